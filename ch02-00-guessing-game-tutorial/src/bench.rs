@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::{
+    config::GameConfig,
+    game::{Game, GuessOutcome},
+    strategy::{self, Strategy},
+};
+
+// Attempt statistics for one strategy over many seeded games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub strategy: &'static str,
+    pub games: usize,
+    pub mean: f64,
+    pub p95: u32,
+    pub worst: u32,
+    // Games that needed more attempts than the config allows.
+    pub over_limit: usize,
+    pub max_attempts: Option<u32>,
+}
+
+impl BenchReport {
+    pub fn within_limit(&self) -> bool {
+        self.over_limit == 0
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = match self.max_attempts {
+            Some(max_attempts) => format!("{}/{} over {max_attempts}", self.over_limit, self.games),
+            None => "no limit".to_string(),
+        };
+        write!(
+            f,
+            "{:<8} games {:>6}  mean {:>8.2}  p95 {:>6}  worst {:>6}  {limit}",
+            self.strategy, self.games, self.mean, self.p95, self.worst
+        )
+    }
+}
+
+// Plays `games` games with the given strategy. Game `i` uses secret seed `seed + i`,
+// so every strategy faces exactly the same secrets.
+//
+// The attempt limit is lifted while playing so that a strategy that would have lost
+// still reports how many attempts it really needed; `over_limit` counts those games.
+pub fn benchmark(
+    config: &GameConfig,
+    strategy: &mut dyn Strategy,
+    games: usize,
+    seed: u64,
+) -> BenchReport {
+    let unlimited = GameConfig::new(config.min(), config.max(), None)
+        .expect("a valid config stays valid without an attempt limit");
+
+    let mut attempts: Vec<u32> = Vec::with_capacity(games);
+    for i in 0..games {
+        let mut game = Game::from_seed(unlimited.clone(), seed.wrapping_add(i as u64));
+        match strategy::solve(&mut game, strategy) {
+            Ok(GuessOutcome::Won { attempts: used }) => attempts.push(used),
+            other => panic!(
+                "{} failed to finish an unlimited game: {other:?}",
+                strategy.name()
+            ),
+        }
+    }
+    attempts.sort_unstable();
+
+    let over_limit = match config.max_attempts() {
+        Some(max_attempts) => attempts.iter().filter(|&&used| used > max_attempts).count(),
+        None => 0,
+    };
+
+    BenchReport {
+        strategy: strategy.name(),
+        games,
+        mean: mean(&attempts),
+        p95: percentile(&attempts, 95),
+        worst: attempts.last().copied().unwrap_or(0),
+        over_limit,
+        max_attempts: config.max_attempts(),
+    }
+}
+
+fn mean(sorted: &[u32]) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.iter().map(|&n| n as f64).sum::<f64>() / sorted.len() as f64
+}
+
+// Nearest-rank percentile: the smallest value that at least `p` percent of games did not exceed.
+fn percentile(sorted: &[u32], p: usize) -> u32 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Difficulty,
+        strategy::{BinarySearch, Linear},
+    };
+
+    #[test]
+    fn presets_are_solvable_by_binary_search() {
+        for difficulty in Difficulty::ALL {
+            let report = benchmark(&difficulty.config(), &mut BinarySearch::default(), 2000, 0);
+            assert!(report.within_limit(), "{difficulty}: {report}");
+        }
+    }
+
+    #[test]
+    fn linear_is_worse_than_binary() {
+        let config = Difficulty::Normal.config();
+        let binary = benchmark(&config, &mut BinarySearch::default(), 500, 7);
+        let linear = benchmark(&config, &mut Linear::default(), 500, 7);
+
+        assert!(linear.mean > binary.mean);
+        assert!(!linear.within_limit());
+        assert_eq!(binary.worst, 7);
+    }
+
+    #[test]
+    fn percentiles() {
+        let sorted: Vec<u32> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 95), 19);
+        assert_eq!(percentile(&sorted, 100), 20);
+        assert_eq!(percentile(&[4], 95), 4);
+        assert_eq!(mean(&[1, 2, 3]), 2.0);
+    }
+}
@@ -0,0 +1,134 @@
+use ch02_00_guessing_game_tutorial::{
+    cli::DEFAULT_SERVER_ADDR,
+    config::GameConfig,
+    net::{
+        client::Client,
+        protocol::{ClientMessage, Hint, ServerMessage},
+    },
+    strategy::{self, Strategy},
+};
+use std::{cmp::Ordering, env, io, process};
+
+const USAGE: &str = "\
+Usage: guessing-client [--addr <host:port>] [--strategy <binary|random|linear>] [--seed <u64>]
+
+Without --strategy the guesses are read from stdin.";
+
+fn main() {
+    let mut addr = DEFAULT_SERVER_ADDR.to_string();
+    let mut strategy: Option<Box<dyn Strategy>> = None;
+    let mut strategy_name = None;
+    let mut seed = 0;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--addr", Some(value)) => addr = value,
+            ("--strategy", Some(value)) => strategy_name = Some(value),
+            ("--seed", Some(value)) => match value.parse() {
+                Ok(value) => seed = value,
+                Err(_) => usage_error(&format!("--seed expects a number, got '{value}'")),
+            },
+            _ => usage_error(&format!("unexpected argument '{arg}'")),
+        }
+    }
+    if let Some(name) = strategy_name {
+        strategy = match strategy::by_name(&name, seed) {
+            Some(strategy) => Some(strategy),
+            None => usage_error(&format!("unknown strategy '{name}'")),
+        };
+    }
+
+    let mut client = match Client::connect(&addr) {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("Error: could not connect to {addr}: {error}");
+            process::exit(1);
+        }
+    };
+
+    match play(&mut client, strategy) {
+        Ok(true) => println!("You win!"),
+        Ok(false) => {}
+        Err(error) => {
+            eprintln!("Error: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+// Follows the server's messages until the game ends and returns whether this client won.
+fn play(client: &mut Client, mut strategy: Option<Box<dyn Strategy>>) -> io::Result<bool> {
+    let mut last_guess = None;
+
+    while let Some(message) = client.recv()? {
+        println!("{message}");
+
+        match message {
+            ServerMessage::Welcome { player, min, max } => {
+                println!("Joined as player {player}, the secret is between {min} and {max}");
+                if let Some(strategy) = strategy.as_deref_mut() {
+                    let config = GameConfig::new(min, max, None)
+                        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                    strategy.start(&config);
+                }
+            }
+            ServerMessage::Turn => {
+                let guess = match strategy.as_deref_mut() {
+                    Some(strategy) => strategy.next_guess(),
+                    None => match read_guess()? {
+                        Some(guess) => guess,
+                        None => return Ok(false),
+                    },
+                };
+                last_guess = Some(guess);
+                client.send(ClientMessage::Guess(guess))?;
+            }
+            ServerMessage::Hint(hint) => {
+                if let (Some(strategy), Some(guess)) = (strategy.as_deref_mut(), last_guess) {
+                    strategy.observe(guess, ordering(hint));
+                }
+            }
+            // Everyone shares one secret, so other players' hints are just as useful.
+            ServerMessage::Played { guess, hint, .. } => {
+                if let Some(strategy) = strategy.as_deref_mut() {
+                    strategy.observe(guess, ordering(hint));
+                }
+            }
+            ServerMessage::Win => return Ok(true),
+            ServerMessage::Over { .. } => return Ok(false),
+            ServerMessage::Error(_) => {}
+        }
+    }
+
+    Ok(false)
+}
+
+fn ordering(hint: Hint) -> Ordering {
+    match hint {
+        Hint::TooSmall => Ordering::Less,
+        Hint::TooBig => Ordering::Greater,
+    }
+}
+
+// Keeps asking until the player types a number; None means stdin was closed.
+fn read_guess() -> io::Result<Option<i32>> {
+    loop {
+        println!("Input your guess.");
+
+        let mut guess = String::new();
+        if io::stdin().read_line(&mut guess)? == 0 {
+            return Ok(None);
+        }
+
+        match guess.trim().parse() {
+            Ok(num) => return Ok(Some(num)),
+            Err(_) => println!("Error, Please input a number"),
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::{fmt, path::PathBuf};
+
+use crate::{
+    config::{ConfigError, Difficulty, GameConfig},
+    scores::DEFAULT_SCORES_FILE,
+    strategy::STRATEGY_NAMES,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Play,
+    Replay(PathBuf),
+    // `difficulty` is only set when --difficulty was given, otherwise every difficulty is listed.
+    ScoresList {
+        difficulty: Option<Difficulty>,
+        top: usize,
+    },
+    ScoresReset,
+    // `strategy` is None to benchmark every built-in strategy.
+    Bench {
+        strategy: Option<String>,
+        games: usize,
+    },
+    Server {
+        addr: String,
+    },
+}
+
+// Options collected from the command line.
+// A difficulty preset is the starting point, and --min, --max and --attempts override parts of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub command: Command,
+    pub difficulty: Difficulty,
+    pub config: GameConfig,
+    pub seed: Option<u64>,
+    pub transcript: Option<PathBuf>,
+    pub player: Option<String>,
+    pub scores_file: PathBuf,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: Command::Play,
+            difficulty: Difficulty::Normal,
+            config: Difficulty::Normal.config(),
+            seed: None,
+            transcript: None,
+            player: None,
+            scores_file: PathBuf::from(DEFAULT_SCORES_FILE),
+        }
+    }
+}
+
+impl Options {
+    // Scores only count when the game was played with an unmodified preset,
+    // otherwise `--max 2` would put anyone at the top of the leaderboard.
+    pub fn is_ranked(&self) -> bool {
+        self.config == self.difficulty.config()
+    }
+}
+
+pub const DEFAULT_SERVER_ADDR: &str = "127.0.0.1:7878";
+
+pub const USAGE: &str = "\
+Usage: guessing-game [OPTIONS]
+       guessing-game scores list [--difficulty <d>] [--top <n>] [--scores <file>]
+       guessing-game scores reset [--scores <file>]
+       guessing-game bench [--strategy <binary|random|linear>] [--games <n>] [--seed <u64>]
+       guessing-game server [--addr <host:port>] [OPTIONS]
+
+Options:
+  --difficulty <easy|normal|hard>  start from a preset (default: normal)
+  --min <n>                        lowest possible secret number
+  --max <n>                        highest possible secret number
+  --attempts <n|unlimited>         how many guesses the player gets
+  --seed <u64>                     pick the secret number from a fixed seed
+  --transcript <file>              save the session so it can be replayed
+  --replay <file>                  replay a saved session and check its outcomes
+  --name <player>                  name to record on the leaderboard
+  --scores <file>                  leaderboard file (default: guessing-game-scores.json)
+  --top <n>                        how many scores `scores list` shows (default: 10)
+  --strategy <name>                strategy for `bench` (default: all of them)
+  --games <n>                      games per strategy for `bench` (default: 10000)
+  --addr <host:port>               where `server` listens (default: 127.0.0.1:7878)
+  --help                           print this message";
+
+pub fn parse_args<I>(args: I) -> Result<Options, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut command = Command::Play;
+    let mut difficulty = None;
+    let mut min = None;
+    let mut max = None;
+    let mut attempts = None;
+    let mut seed = None;
+    let mut transcript = None;
+    let mut player = None;
+    let mut scores_file = None;
+    let mut top = 10;
+    let mut strategy = None;
+    let mut games = 10_000;
+    let mut addr = None;
+
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("scores") => {
+            args.next();
+            command = match args.next().as_deref() {
+                Some("list") => Command::ScoresList {
+                    difficulty: None,
+                    top,
+                },
+                Some("reset") => Command::ScoresReset,
+                Some(other) => return Err(CliError::UnknownArgument(other.to_string())),
+                None => return Err(CliError::MissingValue("scores".to_string())),
+            };
+        }
+        Some("bench") => {
+            args.next();
+            command = Command::Bench {
+                strategy: None,
+                games,
+            };
+        }
+        Some("server") => {
+            args.next();
+            command = Command::Server {
+                addr: DEFAULT_SERVER_ADDR.to_string(),
+            };
+        }
+        _ => {}
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--difficulty" => difficulty = Some(value_for(&arg, args.next())?.parse()?),
+            "--min" => min = Some(number_for(&arg, args.next())?),
+            "--max" => max = Some(number_for(&arg, args.next())?),
+            "--attempts" => {
+                let value = value_for(&arg, args.next())?;
+                attempts = Some(if value == "unlimited" {
+                    None
+                } else {
+                    Some(number_for(&arg, Some(value))?)
+                });
+            }
+            "--seed" => seed = Some(number_for(&arg, args.next())?),
+            "--transcript" => transcript = Some(PathBuf::from(value_for(&arg, args.next())?)),
+            "--replay" => command = Command::Replay(PathBuf::from(value_for(&arg, args.next())?)),
+            "--name" => player = Some(value_for(&arg, args.next())?),
+            "--scores" => scores_file = Some(PathBuf::from(value_for(&arg, args.next())?)),
+            "--top" => top = number_for(&arg, args.next())?,
+            "--strategy" => {
+                let name = value_for(&arg, args.next())?;
+                if !STRATEGY_NAMES.contains(&name.as_str()) {
+                    return Err(CliError::UnknownStrategy(name));
+                }
+                strategy = Some(name);
+            }
+            "--games" => games = number_for(&arg, args.next())?,
+            "--addr" => addr = Some(value_for(&arg, args.next())?),
+            "--help" | "-h" => return Err(CliError::HelpRequested),
+            _ => return Err(CliError::UnknownArgument(arg)),
+        }
+    }
+
+    if let Command::ScoresList {
+        difficulty: filter,
+        top: limit,
+    } = &mut command
+    {
+        *filter = difficulty;
+        *limit = top;
+    }
+    if let Command::Bench {
+        strategy: chosen,
+        games: count,
+    } = &mut command
+    {
+        *chosen = strategy;
+        *count = games;
+    }
+    if let (Command::Server { addr: listen }, Some(addr)) = (&mut command, addr) {
+        *listen = addr;
+    }
+
+    let difficulty = difficulty.unwrap_or(Difficulty::Normal);
+    let preset = difficulty.config();
+    let config = GameConfig::new(
+        min.unwrap_or(preset.min()),
+        max.unwrap_or(preset.max()),
+        attempts.unwrap_or(preset.max_attempts()),
+    )?;
+
+    Ok(Options {
+        command,
+        difficulty,
+        config,
+        seed,
+        transcript,
+        player,
+        scores_file: scores_file.unwrap_or_else(|| PathBuf::from(DEFAULT_SCORES_FILE)),
+    })
+}
+
+fn value_for(flag: &str, value: Option<String>) -> Result<String, CliError> {
+    value.ok_or_else(|| CliError::MissingValue(flag.to_string()))
+}
+
+fn number_for<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, CliError> {
+    let value = value_for(flag, value)?;
+    value.parse().map_err(|_| CliError::InvalidNumber {
+        flag: flag.to_string(),
+        value,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    HelpRequested,
+    UnknownArgument(String),
+    MissingValue(String),
+    InvalidNumber { flag: String, value: String },
+    UnknownStrategy(String),
+    Config(ConfigError),
+}
+
+impl From<ConfigError> for CliError {
+    fn from(error: ConfigError) -> Self {
+        CliError::Config(error)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::HelpRequested => write!(f, "{USAGE}"),
+            CliError::UnknownArgument(arg) => write!(f, "unknown argument '{arg}'"),
+            CliError::MissingValue(flag) => write!(f, "{flag} needs a value"),
+            CliError::InvalidNumber { flag, value } => {
+                write!(f, "{flag} expects a number, got '{value}'")
+            }
+            CliError::UnknownStrategy(name) => write!(
+                f,
+                "unknown strategy '{name}', expected one of {}",
+                STRATEGY_NAMES.join(", ")
+            ),
+            CliError::Config(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, CliError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults_to_normal() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_preset() {
+        let options = parse(&[
+            "--difficulty",
+            "hard",
+            "--max",
+            "500",
+            "--attempts",
+            "unlimited",
+        ])
+        .unwrap();
+
+        assert_eq!(options.difficulty, Difficulty::Hard);
+        assert_eq!(options.config, GameConfig::new(1, 500, None).unwrap());
+    }
+
+    #[test]
+    fn parses_seed_and_files() {
+        let options = parse(&["--seed", "42", "--transcript", "session.txt"]).unwrap();
+
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.transcript, Some(PathBuf::from("session.txt")));
+        assert_eq!(options.command, Command::Play);
+        assert!(options.is_ranked());
+    }
+
+    #[test]
+    fn parses_scores_subcommands() {
+        let options = parse(&["scores", "list", "--difficulty", "easy", "--top", "3"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::ScoresList {
+                difficulty: Some(Difficulty::Easy),
+                top: 3
+            }
+        );
+
+        let options = parse(&["scores", "reset", "--scores", "board.json"]).unwrap();
+        assert_eq!(options.command, Command::ScoresReset);
+        assert_eq!(options.scores_file, PathBuf::from("board.json"));
+
+        assert_eq!(
+            parse(&["scores", "delete"]),
+            Err(CliError::UnknownArgument("delete".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_bench() {
+        let options = parse(&["bench", "--strategy", "binary", "--games", "50"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Bench {
+                strategy: Some("binary".to_string()),
+                games: 50
+            }
+        );
+
+        assert_eq!(
+            parse(&["bench", "--strategy", "psychic"]),
+            Err(CliError::UnknownStrategy("psychic".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_server() {
+        assert_eq!(
+            parse(&["server"]).unwrap().command,
+            Command::Server {
+                addr: DEFAULT_SERVER_ADDR.to_string()
+            }
+        );
+        assert_eq!(
+            parse(&["server", "--addr", "0.0.0.0:9000"])
+                .unwrap()
+                .command,
+            Command::Server {
+                addr: "0.0.0.0:9000".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn custom_ranges_are_not_ranked() {
+        assert!(!parse(&["--max", "2"]).unwrap().is_ranked());
+    }
+
+    #[test]
+    fn reports_bad_arguments() {
+        assert_eq!(
+            parse(&["--min", "ten"]),
+            Err(CliError::InvalidNumber {
+                flag: "--min".to_string(),
+                value: "ten".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["--max"]),
+            Err(CliError::MissingValue("--max".to_string()))
+        );
+        assert_eq!(
+            parse(&["--min", "50", "--max", "10"]),
+            Err(CliError::Config(ConfigError::EmptyRange {
+                min: 50,
+                max: 10
+            }))
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+// Difficulty presets bundle a range and an attempt limit.
+// Every preset stays solvable by binary search within its attempt limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn config(self) -> GameConfig {
+        match self {
+            Difficulty::Easy => GameConfig {
+                min: 1,
+                max: 50,
+                max_attempts: None,
+            },
+            Difficulty::Normal => GameConfig {
+                min: 1,
+                max: 100,
+                max_attempts: Some(10),
+            },
+            Difficulty::Hard => GameConfig {
+                min: 1,
+                max: 1000,
+                max_attempts: Some(10),
+            },
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(ConfigError::UnknownDifficulty(s.to_string())),
+        }
+    }
+}
+
+// The fields are private so that a GameConfig can only be built through `new` or a preset,
+// which guarantees that `min <= max` and that the attempt limit is not zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameConfig {
+    min: i32,
+    max: i32,
+    max_attempts: Option<u32>,
+}
+
+impl GameConfig {
+    pub fn new(min: i32, max: i32, max_attempts: Option<u32>) -> Result<GameConfig, ConfigError> {
+        if min > max {
+            return Err(ConfigError::EmptyRange { min, max });
+        }
+        if max_attempts == Some(0) {
+            return Err(ConfigError::ZeroAttempts);
+        }
+
+        Ok(GameConfig {
+            min,
+            max,
+            max_attempts,
+        })
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn range(&self) -> RangeInclusive<i32> {
+        self.min..=self.max
+    }
+
+    // `None` means the player can keep guessing until they win.
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        self.range().contains(&value)
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Difficulty::Normal.config()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    EmptyRange { min: i32, max: i32 },
+    ZeroAttempts,
+    UnknownDifficulty(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyRange { min, max } => {
+                write!(f, "range {min}..={max} is empty, min must not exceed max")
+            }
+            ConfigError::ZeroAttempts => write!(f, "max attempts must be at least 1"),
+            ConfigError::UnknownDifficulty(name) => {
+                write!(
+                    f,
+                    "unknown difficulty '{name}', expected easy, normal or hard"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid_configs() {
+        for difficulty in Difficulty::ALL {
+            let preset = difficulty.config();
+            let rebuilt = GameConfig::new(preset.min(), preset.max(), preset.max_attempts());
+            assert_eq!(rebuilt, Ok(preset));
+        }
+    }
+
+    #[test]
+    fn rejects_empty_range_and_zero_attempts() {
+        assert_eq!(
+            GameConfig::new(10, 1, None),
+            Err(ConfigError::EmptyRange { min: 10, max: 1 })
+        );
+        assert_eq!(
+            GameConfig::new(1, 10, Some(0)),
+            Err(ConfigError::ZeroAttempts)
+        );
+    }
+
+    #[test]
+    fn parses_difficulty_names() {
+        assert_eq!("HARD".parse(), Ok(Difficulty::Hard));
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+}
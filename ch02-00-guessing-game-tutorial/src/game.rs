@@ -0,0 +1,269 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{cmp::Ordering, fmt};
+
+use crate::config::GameConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+// What a single guess did to the game.
+// `attempts_left` is `None` when the config has no attempt limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessOutcome {
+    TooSmall { attempts_left: Option<u32> },
+    TooBig { attempts_left: Option<u32> },
+    Won { attempts: u32 },
+    Lost { secret: i32 },
+}
+
+impl GuessOutcome {
+    // The same feedback the original loop got from `guess.cmp(&secret_number)`.
+    // A losing guess returns None because the game is over and the hint no longer matters.
+    pub fn ordering(&self) -> Option<Ordering> {
+        match self {
+            GuessOutcome::TooSmall { .. } => Some(Ordering::Less),
+            GuessOutcome::TooBig { .. } => Some(Ordering::Greater),
+            GuessOutcome::Won { .. } => Some(Ordering::Equal),
+            GuessOutcome::Lost { .. } => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, GuessOutcome::Won { .. } | GuessOutcome::Lost { .. })
+    }
+}
+
+impl fmt::Display for GuessOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessOutcome::TooSmall { .. } => write!(f, "Too small!"),
+            GuessOutcome::TooBig { .. } => write!(f, "Too big!"),
+            GuessOutcome::Won { attempts } => {
+                write!(f, "You win! It took you {attempts} attempts.")
+            }
+            GuessOutcome::Lost { secret } => write!(f, "You lose! The secret number was {secret}."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    OutOfRange { value: i32, min: i32, max: i32 },
+    GameOver,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::OutOfRange { value, min, max } => {
+                write!(f, "{value} is outside the range {min}..={max}")
+            }
+            GuessError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+// A Game owns the secret and moves from InProgress to Won or Lost.
+// It never reads input or prints anything, so it can be driven by a CLI, a test or another binary.
+#[derive(Debug, Clone)]
+pub struct Game {
+    config: GameConfig,
+    secret: i32,
+    attempts: u32,
+    state: GameState,
+}
+
+impl Game {
+    pub fn new<R: Rng + ?Sized>(config: GameConfig, rng: &mut R) -> Game {
+        let secret = rng.random_range(config.range());
+
+        Game {
+            config,
+            secret,
+            attempts: 0,
+            state: GameState::InProgress,
+        }
+    }
+
+    // ChaCha8 is used instead of `StdRng` because its output is stable across rand releases,
+    // so a seed recorded today still produces the same secret later.
+    pub fn from_seed(config: GameConfig, seed: u64) -> Game {
+        Game::new(config, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    pub fn with_secret(config: GameConfig, secret: i32) -> Result<Game, GuessError> {
+        if !config.contains(secret) {
+            return Err(GuessError::OutOfRange {
+                value: secret,
+                min: config.min(),
+                max: config.max(),
+            });
+        }
+
+        Ok(Game {
+            config,
+            secret,
+            attempts: 0,
+            state: GameState::InProgress,
+        })
+    }
+
+    // Out-of-range guesses are rejected without using up an attempt.
+    pub fn guess(&mut self, value: i32) -> Result<GuessOutcome, GuessError> {
+        if self.state != GameState::InProgress {
+            return Err(GuessError::GameOver);
+        }
+        if !self.config.contains(value) {
+            return Err(GuessError::OutOfRange {
+                value,
+                min: self.config.min(),
+                max: self.config.max(),
+            });
+        }
+
+        self.attempts += 1;
+
+        let outcome = match value.cmp(&self.secret) {
+            Ordering::Equal => {
+                self.state = GameState::Won;
+                GuessOutcome::Won {
+                    attempts: self.attempts,
+                }
+            }
+            _ if self.attempts_left() == Some(0) => {
+                self.state = GameState::Lost;
+                GuessOutcome::Lost {
+                    secret: self.secret,
+                }
+            }
+            Ordering::Less => GuessOutcome::TooSmall {
+                attempts_left: self.attempts_left(),
+            },
+            Ordering::Greater => GuessOutcome::TooBig {
+                attempts_left: self.attempts_left(),
+            },
+        };
+
+        Ok(outcome)
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.config
+            .max_attempts()
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    // Only reveal the secret once the game is over.
+    pub fn secret(&self) -> Option<i32> {
+        match self.state {
+            GameState::InProgress => None,
+            GameState::Won | GameState::Lost => Some(self.secret),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Difficulty;
+
+    fn game(min: i32, max: i32, max_attempts: Option<u32>, secret: i32) -> Game {
+        let config = GameConfig::new(min, max, max_attempts).unwrap();
+        Game::with_secret(config, secret).unwrap()
+    }
+
+    #[test]
+    fn hints_then_wins() {
+        let mut game = game(1, 100, None, 42);
+
+        assert_eq!(
+            game.guess(10),
+            Ok(GuessOutcome::TooSmall {
+                attempts_left: None
+            })
+        );
+        assert_eq!(
+            game.guess(90),
+            Ok(GuessOutcome::TooBig {
+                attempts_left: None
+            })
+        );
+        assert_eq!(game.secret(), None);
+        assert_eq!(game.guess(42), Ok(GuessOutcome::Won { attempts: 3 }));
+        assert_eq!(game.state(), GameState::Won);
+        assert_eq!(game.secret(), Some(42));
+    }
+
+    #[test]
+    fn loses_when_attempts_run_out() {
+        let mut game = game(1, 10, Some(2), 7);
+
+        assert_eq!(
+            game.guess(1),
+            Ok(GuessOutcome::TooSmall {
+                attempts_left: Some(1)
+            })
+        );
+        assert_eq!(game.guess(2), Ok(GuessOutcome::Lost { secret: 7 }));
+        assert_eq!(game.guess(7), Err(GuessError::GameOver));
+    }
+
+    #[test]
+    fn out_of_range_guess_does_not_cost_an_attempt() {
+        let mut game = game(1, 10, Some(1), 5);
+
+        assert_eq!(
+            game.guess(11),
+            Err(GuessError::OutOfRange {
+                value: 11,
+                min: 1,
+                max: 10
+            })
+        );
+        assert_eq!(game.attempts(), 0);
+        assert_eq!(game.guess(5), Ok(GuessOutcome::Won { attempts: 1 }));
+    }
+
+    #[test]
+    fn random_secret_stays_in_range() {
+        let config = GameConfig::new(3, 5, None).unwrap();
+        let mut rng = rand::rng();
+
+        for _ in 0..100 {
+            let mut game = Game::new(config.clone(), &mut rng);
+            let won = (3..=5).any(|value| game.guess(value).unwrap().is_finished());
+            assert!(won);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_secret() {
+        let config = Difficulty::Hard.config();
+
+        for seed in [0, 1, 42, u64::MAX] {
+            let mut first = Game::from_seed(config.clone(), seed);
+            let mut second = Game::from_seed(config.clone(), seed);
+            assert_eq!(first.guess(500), second.guess(500));
+            assert_eq!(first.secret, second.secret);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod bench;
+pub mod cli;
+pub mod config;
+pub mod game;
+pub mod net;
+pub mod scores;
+pub mod strategy;
+pub mod transcript;
@@ -1,20 +1,135 @@
-use rand::Rng;
-use std::{cmp::Ordering, io};
+use ch02_00_guessing_game_tutorial::{
+    bench,
+    cli::{self, CliError, Command, Options},
+    config::Difficulty,
+    game::{Game, GuessOutcome},
+    net::server::Server,
+    scores::{Leaderboard, LoadStatus, Score, ScoresError},
+    strategy::{self, STRATEGY_NAMES},
+    transcript::{ParseError, Transcript},
+};
+use std::{
+    env, fs, io,
+    path::Path,
+    process,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 fn main() {
-    println!("Guess the number!");
+    let options = match cli::parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(CliError::HelpRequested) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(error) => {
+            eprintln!("Error: {error}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match &options.command {
+        Command::Play => play(&options),
+        Command::Replay(path) => replay(path),
+        Command::ScoresList { difficulty, top } => {
+            list_scores(&options.scores_file, *difficulty, *top)
+        }
+        Command::ScoresReset => reset_scores(&options.scores_file),
+        Command::Bench { strategy, games } => run_bench(&options, strategy.as_deref(), *games),
+        Command::Server { addr } => run_server(&options, addr),
+    }
+}
+
+fn run_server(options: &Options, addr: &str) {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let game = Game::from_seed(options.config.clone(), seed);
+
+    let result = Server::bind(addr, game).and_then(|server| {
+        println!(
+            "Hosting a game between {} and {} on {}",
+            options.config.min(),
+            options.config.max(),
+            server.local_addr()?
+        );
+        server.run()
+    });
+
+    match result {
+        Ok(summary) => match summary.winner {
+            Some(player) => println!(
+                "Player {player} guessed {} after {} attempts",
+                summary.secret, summary.attempts
+            ),
+            None => println!("Nobody guessed {}, the attempts ran out", summary.secret),
+        },
+        Err(error) => {
+            eprintln!("Error: server failed: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn run_bench(options: &Options, strategy: Option<&str>, games: usize) {
+    let seed = options.seed.unwrap_or(0);
+    let names = match strategy {
+        Some(name) => vec![name],
+        None => STRATEGY_NAMES.to_vec(),
+    };
+
+    println!(
+        "Benchmarking {games} games on {}..={} (seed {seed})",
+        options.config.min(),
+        options.config.max()
+    );
 
-    let secret_number = rand::rng().random_range(1..=100);
+    let mut all_within_limit = true;
+    for name in names {
+        let mut strategy =
+            strategy::by_name(name, seed).expect("strategy names are validated by the cli");
+        let report = bench::benchmark(&options.config, strategy.as_mut(), games, seed);
+        all_within_limit &= report.within_limit();
+        println!("{report}");
+    }
+
+    if !all_within_limit {
+        println!("Some strategies needed more attempts than the config allows.");
+    }
+}
+
+fn play(options: &Options) {
+    let config = options.config.clone();
+    println!(
+        "Guess the number between {} and {}!",
+        config.min(),
+        config.max()
+    );
+    if let Some(max_attempts) = config.max_attempts() {
+        println!(
+            "You have {max_attempts} attempts ({} mode).",
+            options.difficulty
+        );
+    }
+
+    // Every game is seeded, even without --seed, so any session can be saved and replayed later.
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut game = Game::from_seed(config.clone(), seed);
+    let mut transcript = Transcript::new(seed, config);
+    let started = Instant::now();
 
     loop {
         println!("Input your guess.");
 
         let mut guess = String::new();
 
-        io::stdin()
+        let bytes_read = io::stdin()
             .read_line(&mut guess)
             .expect("Failed to read line");
 
+        if bytes_read == 0 {
+            println!("No more input, bye!");
+            break;
+        }
+
         let guess: i32 = match guess.trim().parse() {
             Ok(num) => num,
             Err(_) => {
@@ -25,13 +140,144 @@ fn main() {
 
         println!("You guessed : {guess}");
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+        match game.guess(guess) {
+            Ok(outcome) => {
+                transcript.record(guess, outcome);
+                println!("{outcome}");
+                if let GuessOutcome::Won { attempts } = outcome {
+                    record_score(options, attempts, started.elapsed());
+                }
+                if outcome.is_finished() {
+                    break;
+                }
             }
+            Err(error) => println!("Error, {error}"),
+        }
+    }
+
+    if let Some(path) = &options.transcript {
+        match fs::write(path, transcript.to_string()) {
+            Ok(()) => println!("Transcript saved to {} (seed {seed})", path.display()),
+            Err(error) => {
+                eprintln!("Error: could not write {}: {error}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn record_score(options: &Options, attempts: u32, elapsed: Duration) {
+    if !options.is_ranked() {
+        println!("Custom game settings, score not recorded.");
+        return;
+    }
+
+    let player = options
+        .player
+        .clone()
+        .or_else(|| env::var("USER").ok())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let recorded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let score = Score {
+        player,
+        difficulty: options.difficulty,
+        attempts,
+        elapsed_ms: elapsed.as_millis() as u64,
+        recorded_at,
+    };
+
+    let result = load_scores(&options.scores_file).and_then(|mut leaderboard| {
+        let personal_best = leaderboard.submit(score);
+        leaderboard.save(&options.scores_file)?;
+        Ok(personal_best)
+    });
+
+    match result {
+        Ok(true) => println!("New personal best!"),
+        Ok(false) => {}
+        Err(error) => eprintln!("Error: could not update the leaderboard: {error}"),
+    }
+}
+
+fn load_scores(path: &Path) -> Result<Leaderboard, ScoresError> {
+    let (leaderboard, status) = Leaderboard::load(path)?;
+    if let LoadStatus::Recovered { backup } = status {
+        eprintln!(
+            "Warning: {} was corrupted, it was moved to {} and a new leaderboard was started",
+            path.display(),
+            backup.display()
+        );
+    }
+    Ok(leaderboard)
+}
+
+fn list_scores(path: &Path, difficulty: Option<Difficulty>, top: usize) {
+    let leaderboard = match Leaderboard::read(path) {
+        Ok(leaderboard) => leaderboard,
+        Err(error) => {
+            eprintln!("Error: could not read {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+
+    let difficulties = match difficulty {
+        Some(difficulty) => vec![difficulty],
+        None => Difficulty::ALL.to_vec(),
+    };
+
+    for difficulty in difficulties {
+        println!("{difficulty}:");
+        let scores = leaderboard.top(difficulty, top);
+        if scores.is_empty() {
+            println!("    no scores yet");
+        }
+        for (rank, score) in scores.iter().enumerate() {
+            println!(
+                "    {:>2}. {:<16} {:>3} attempts {:>8.1}s",
+                rank + 1,
+                score.player,
+                score.attempts,
+                score.elapsed_ms as f64 / 1000.0
+            );
+        }
+    }
+}
+
+fn reset_scores(path: &Path) {
+    match Leaderboard::reset(path) {
+        Ok(()) => println!("Leaderboard {} cleared", path.display()),
+        Err(error) => {
+            eprintln!("Error: could not clear {}: {error}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn replay(path: &Path) {
+    let transcript: Transcript = match fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| text.parse().map_err(|error: ParseError| error.to_string()))
+    {
+        Ok(transcript) => transcript,
+        Err(error) => {
+            eprintln!("Error: could not load {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+
+    match transcript.replay() {
+        Ok(game) => println!(
+            "Replay OK: {} guesses, game is {:?}",
+            transcript.entries.len(),
+            game.state()
+        ),
+        Err(error) => {
+            eprintln!("Replay FAILED: {error}");
+            process::exit(1);
         }
     }
 }
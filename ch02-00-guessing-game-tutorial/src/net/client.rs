@@ -0,0 +1,39 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+use crate::net::protocol::{ClientMessage, ServerMessage};
+
+// A thin wrapper around the connection that speaks the line protocol in `net::protocol`.
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Client> {
+        let writer = TcpStream::connect(addr)?;
+        writer.set_nodelay(true)?;
+        let reader = BufReader::new(writer.try_clone()?);
+
+        Ok(Client { reader, writer })
+    }
+
+    pub fn send(&mut self, message: ClientMessage) -> io::Result<()> {
+        writeln!(self.writer, "{message}")
+    }
+
+    // Returns None once the server has closed the connection.
+    pub fn recv(&mut self) -> io::Result<Option<ServerMessage>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        line.trim()
+            .parse()
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
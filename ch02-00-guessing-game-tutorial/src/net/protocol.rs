@@ -0,0 +1,205 @@
+use std::{fmt, str::FromStr};
+
+// The multiplayer protocol is one message per line, plain ASCII, so it can be driven with `nc` too.
+//
+// client -> server:
+//   GUESS <n>
+//
+// server -> client:
+//   WELCOME <player> <min> <max>     sent once after connecting
+//   TURN                             it is your turn to guess
+//   TOO_SMALL | TOO_BIG              the answer to your guess
+//   PLAYED <player> <n> <hint>       another player guessed, hint is TOO_SMALL or TOO_BIG
+//   WIN                              you guessed the secret
+//   OVER <player|NONE> <secret>      the game ended, NONE means the attempts ran out
+//   ERROR <message>                  the last message was rejected
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientMessage {
+    Guess(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooSmall,
+    TooBig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    Welcome { player: u32, min: i32, max: i32 },
+    Turn,
+    Hint(Hint),
+    Played { player: u32, guess: i32, hint: Hint },
+    Win,
+    Over { winner: Option<u32>, secret: i32 },
+    Error(String),
+}
+
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientMessage::Guess(n) => write!(f, "GUESS {n}"),
+        }
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+
+        match parts.as_slice() {
+            ["GUESS", n] => n
+                .parse()
+                .map(ClientMessage::Guess)
+                .map_err(|_| ProtocolError(s.to_string())),
+            _ => Err(ProtocolError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hint::TooSmall => write!(f, "TOO_SMALL"),
+            Hint::TooBig => write!(f, "TOO_BIG"),
+        }
+    }
+}
+
+impl FromStr for Hint {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TOO_SMALL" => Ok(Hint::TooSmall),
+            "TOO_BIG" => Ok(Hint::TooBig),
+            _ => Err(ProtocolError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerMessage::Welcome { player, min, max } => {
+                write!(f, "WELCOME {player} {min} {max}")
+            }
+            ServerMessage::Turn => write!(f, "TURN"),
+            ServerMessage::Hint(hint) => write!(f, "{hint}"),
+            ServerMessage::Played {
+                player,
+                guess,
+                hint,
+            } => write!(f, "PLAYED {player} {guess} {hint}"),
+            ServerMessage::Win => write!(f, "WIN"),
+            ServerMessage::Over {
+                winner: Some(player),
+                secret,
+            } => write!(f, "OVER {player} {secret}"),
+            ServerMessage::Over {
+                winner: None,
+                secret,
+            } => write!(f, "OVER NONE {secret}"),
+            ServerMessage::Error(message) => write!(f, "ERROR {message}"),
+        }
+    }
+}
+
+impl FromStr for ServerMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ProtocolError(s.to_string());
+
+        if let Some(message) = s.strip_prefix("ERROR ") {
+            return Ok(ServerMessage::Error(message.to_string()));
+        }
+
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let message = match parts.as_slice() {
+            ["WELCOME", player, min, max] => ServerMessage::Welcome {
+                player: player.parse().map_err(|_| invalid())?,
+                min: min.parse().map_err(|_| invalid())?,
+                max: max.parse().map_err(|_| invalid())?,
+            },
+            ["TURN"] => ServerMessage::Turn,
+            [hint @ ("TOO_SMALL" | "TOO_BIG")] => ServerMessage::Hint(hint.parse()?),
+            ["PLAYED", player, guess, hint] => ServerMessage::Played {
+                player: player.parse().map_err(|_| invalid())?,
+                guess: guess.parse().map_err(|_| invalid())?,
+                hint: hint.parse()?,
+            },
+            ["WIN"] => ServerMessage::Win,
+            ["OVER", "NONE", secret] => ServerMessage::Over {
+                winner: None,
+                secret: secret.parse().map_err(|_| invalid())?,
+            },
+            ["OVER", player, secret] => ServerMessage::Over {
+                winner: Some(player.parse().map_err(|_| invalid())?),
+                secret: secret.parse().map_err(|_| invalid())?,
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError(pub String);
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed message '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_messages_round_trip() {
+        let messages = [
+            ServerMessage::Welcome {
+                player: 2,
+                min: -10,
+                max: 10,
+            },
+            ServerMessage::Turn,
+            ServerMessage::Hint(Hint::TooBig),
+            ServerMessage::Played {
+                player: 1,
+                guess: 50,
+                hint: Hint::TooSmall,
+            },
+            ServerMessage::Win,
+            ServerMessage::Over {
+                winner: Some(3),
+                secret: 42,
+            },
+            ServerMessage::Over {
+                winner: None,
+                secret: 7,
+            },
+            ServerMessage::Error("not your turn".to_string()),
+        ];
+
+        for message in messages {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+    }
+
+    #[test]
+    fn parses_client_messages() {
+        assert_eq!("GUESS 12".parse(), Ok(ClientMessage::Guess(12)));
+        assert_eq!(ClientMessage::Guess(-3).to_string(), "GUESS -3");
+        assert!("GUESS twelve".parse::<ClientMessage>().is_err());
+        assert!("HELLO".parse::<ClientMessage>().is_err());
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    game::{Game, GuessError, GuessOutcome},
+    net::protocol::{ClientMessage, Hint, ServerMessage},
+};
+
+// How long one write to a player may take. A client that stops reading fills its socket
+// buffer, and past this its connection is closed rather than its writer waiting forever.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// How the hosted game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub winner: Option<u32>,
+    pub secret: i32,
+    pub attempts: u32,
+}
+
+// Hosts a single game for any number of TCP clients. Players take turns in the order they joined,
+// and every guess counts against the same attempt limit.
+pub struct Server {
+    listener: TcpListener,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, game: Game) -> io::Result<Server> {
+        let listener = TcpListener::bind(addr)?;
+
+        Ok(Server {
+            listener,
+            lobby: Arc::new(Mutex::new(Lobby::new(game))),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Accepts players until the game is over, then disconnects everyone and returns the result.
+    //
+    // The listener is polled in non-blocking mode so that the accept loop can notice
+    // the end of the game instead of blocking forever on the next connection.
+    pub fn run(self) -> io::Result<Summary> {
+        self.listener.set_nonblocking(true)?;
+        let mut writers: Vec<JoinHandle<()>> = Vec::new();
+
+        loop {
+            let summary = self.lobby.lock().unwrap().summary;
+            if let Some(summary) = summary {
+                // Every player's last messages are still queued; deliver them before returning,
+                // since the caller may exit as soon as it has the result.
+                for writer in writers {
+                    let _ = writer.join();
+                }
+                return Ok(summary);
+            }
+
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+                    // Messages are tiny and strictly request/response, so don't let Nagle batch them.
+                    stream.set_nodelay(true)?;
+                    let (outbox, writer) = spawn_writer(stream.try_clone()?)?;
+                    writers.push(writer);
+                    let lobby = Arc::clone(&self.lobby);
+                    thread::spawn(move || handle_client(lobby, stream, outbox));
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+// Writes each queued line to the player on a thread of its own, so that a slow client holds
+// up nobody else. The connection is closed once the queue's sender is dropped, when the
+// player leaves or the game ends, or when a write fails, which also ends the player's reader.
+fn spawn_writer(mut stream: TcpStream) -> io::Result<(Sender<String>, JoinHandle<()>)> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let (outbox, queue) = mpsc::channel::<String>();
+    let writer = thread::spawn(move || {
+        for line in queue {
+            if writeln!(stream, "{line}").is_err() {
+                break;
+            }
+        }
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+    Ok((outbox, writer))
+}
+
+fn handle_client(lobby: Arc<Mutex<Lobby>>, stream: TcpStream, outbox: Sender<String>) {
+    let Some(player) = lobby.lock().unwrap().join(outbox) else {
+        return;
+    };
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        let mut lobby = lobby.lock().unwrap();
+
+        match line.trim().parse() {
+            Ok(ClientMessage::Guess(n)) => lobby.guess(player, n),
+            Err(error) => lobby.send(player, &ServerMessage::Error(error.to_string())),
+        }
+    }
+
+    lobby.lock().unwrap().leave(player);
+}
+
+// Shared state behind the server's mutex. Every method runs with the lock held,
+// so turns and the game itself can't race between client threads. Messages only go into
+// each player's queue, so holding the lock never waits on the network.
+struct Lobby {
+    game: Game,
+    players: Vec<(u32, Sender<String>)>,
+    turn: usize,
+    next_id: u32,
+    summary: Option<Summary>,
+}
+
+impl Lobby {
+    fn new(game: Game) -> Lobby {
+        Lobby {
+            game,
+            players: Vec::new(),
+            turn: 0,
+            next_id: 1,
+            summary: None,
+        }
+    }
+
+    // Seats a new player, or turns them away with None if the game has already ended.
+    fn join(&mut self, outbox: Sender<String>) -> Option<u32> {
+        if self.summary.is_some() {
+            let over = ServerMessage::Error("the game is over".to_string());
+            let _ = outbox.send(over.to_string());
+            return None;
+        }
+
+        let player = self.next_id;
+        self.next_id += 1;
+        self.players.push((player, outbox));
+
+        let config = self.game.config();
+        let welcome = ServerMessage::Welcome {
+            player,
+            min: config.min(),
+            max: config.max(),
+        };
+        self.send(player, &welcome);
+
+        if self.players.len() == 1 {
+            self.turn = 0;
+            self.send(player, &ServerMessage::Turn);
+        }
+
+        Some(player)
+    }
+
+    fn leave(&mut self, player: u32) {
+        let Some(index) = self.players.iter().position(|(id, _)| *id == player) else {
+            return;
+        };
+        let was_current = index == self.turn;
+        self.players.remove(index);
+
+        if self.players.is_empty() || self.summary.is_some() {
+            self.turn = 0;
+            return;
+        }
+        if index < self.turn {
+            self.turn -= 1;
+        }
+        self.turn %= self.players.len();
+        if was_current {
+            self.send_turn();
+        }
+    }
+
+    fn guess(&mut self, player: u32, n: i32) {
+        if self.players.get(self.turn).map(|(id, _)| *id) != Some(player) {
+            self.send(player, &ServerMessage::Error("not your turn".to_string()));
+            return;
+        }
+
+        let outcome = match self.game.guess(n) {
+            Ok(outcome) => outcome,
+            Err(error @ GuessError::OutOfRange { .. }) => {
+                self.send(player, &ServerMessage::Error(error.to_string()));
+                self.send(player, &ServerMessage::Turn);
+                return;
+            }
+            Err(error @ GuessError::GameOver) => {
+                self.send(player, &ServerMessage::Error(error.to_string()));
+                return;
+            }
+        };
+
+        match outcome {
+            GuessOutcome::TooSmall { .. } => self.pass_turn(player, n, Hint::TooSmall),
+            GuessOutcome::TooBig { .. } => self.pass_turn(player, n, Hint::TooBig),
+            GuessOutcome::Won { attempts } => {
+                let secret = n;
+                self.send(player, &ServerMessage::Win);
+                let over = ServerMessage::Over {
+                    winner: Some(player),
+                    secret,
+                };
+                self.broadcast(&over, Some(player));
+                self.finish(Summary {
+                    winner: Some(player),
+                    secret,
+                    attempts,
+                });
+            }
+            GuessOutcome::Lost { secret } => {
+                self.broadcast(
+                    &ServerMessage::Over {
+                        winner: None,
+                        secret,
+                    },
+                    None,
+                );
+                self.finish(Summary {
+                    winner: None,
+                    secret,
+                    attempts: self.game.attempts(),
+                });
+            }
+        }
+    }
+
+    // Answers the guesser, tells everyone else what was tried and hands the turn to the next player.
+    fn pass_turn(&mut self, player: u32, guess: i32, hint: Hint) {
+        self.send(player, &ServerMessage::Hint(hint));
+        self.broadcast(
+            &ServerMessage::Played {
+                player,
+                guess,
+                hint,
+            },
+            Some(player),
+        );
+        self.turn = (self.turn + 1) % self.players.len();
+        self.send_turn();
+    }
+
+    // Dropping the queues lets each writer send what is left and then close the connection.
+    fn finish(&mut self, summary: Summary) {
+        self.summary = Some(summary);
+        self.players.clear();
+    }
+
+    fn send_turn(&mut self) {
+        if let Some((player, _)) = self.players.get(self.turn) {
+            let player = *player;
+            self.send(player, &ServerMessage::Turn);
+        }
+    }
+
+    // Send errors are ignored here: a writer only stops when its connection is broken, and
+    // that player is cleaned up by its own reader thread.
+    fn send(&mut self, player: u32, message: &ServerMessage) {
+        if let Some((_, outbox)) = self.players.iter().find(|(id, _)| *id == player) {
+            let _ = outbox.send(message.to_string());
+        }
+    }
+
+    fn broadcast(&mut self, message: &ServerMessage, except: Option<u32>) {
+        for (id, outbox) in &self.players {
+            if Some(*id) != except {
+                let _ = outbox.send(message.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    fn lobby() -> Lobby {
+        Lobby::new(Game::from_seed(GameConfig::new(1, 10, None).unwrap(), 1))
+    }
+
+    #[test]
+    fn players_who_join_after_the_end_are_turned_away() {
+        let mut lobby = lobby();
+        let (outbox, queue) = mpsc::channel();
+        assert_eq!(lobby.join(outbox), Some(1));
+        assert_eq!(queue.try_iter().count(), 2);
+
+        lobby.finish(Summary {
+            winner: Some(1),
+            secret: 4,
+            attempts: 3,
+        });
+        // Finishing drops the queues, which is what closes the connections.
+        assert!(queue.recv().is_err());
+
+        let (outbox, queue) = mpsc::channel();
+        assert_eq!(lobby.join(outbox), None);
+        assert_eq!(
+            queue.try_iter().collect::<Vec<_>>(),
+            ["ERROR the game is over"]
+        );
+    }
+
+    #[test]
+    fn a_player_who_stops_reading_holds_up_no_one() {
+        let mut lobby = lobby();
+        let (stuck, _never_read) = mpsc::channel();
+        let (outbox, queue) = mpsc::channel();
+        lobby.join(stuck);
+        lobby.join(outbox);
+
+        // Far more than a socket buffer holds, all queued without blocking.
+        for _ in 0..100_000 {
+            lobby.broadcast(&ServerMessage::Turn, None);
+        }
+        assert_eq!(queue.try_iter().count(), 100_001);
+    }
+}
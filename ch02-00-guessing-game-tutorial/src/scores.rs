@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    ffi::OsString,
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::config::Difficulty;
+
+pub const DEFAULT_SCORES_FILE: &str = "guessing-game-scores.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub player: String,
+    pub difficulty: Difficulty,
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+    // Seconds since the Unix epoch, used to break ties in favour of whoever got there first.
+    pub recorded_at: u64,
+}
+
+impl Score {
+    // Fewer attempts win, then the faster game, then the older record.
+    fn rank(&self, other: &Score) -> Ordering {
+        self.attempts
+            .cmp(&other.attempts)
+            .then(self.elapsed_ms.cmp(&other.elapsed_ms))
+            .then(self.recorded_at.cmp(&other.recorded_at))
+    }
+}
+
+// The leaderboard keeps one entry per (player, difficulty): that player's best game.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    scores: Vec<Score>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadStatus {
+    Missing,
+    Loaded,
+    // The file could not be parsed, so it was moved aside and an empty leaderboard was started.
+    Recovered { backup: PathBuf },
+}
+
+impl Leaderboard {
+    // Reads the leaderboard without changing the file, for commands that only show it.
+    // A missing file is an empty leaderboard; a corrupt one is an error.
+    pub fn read(path: &Path) -> Result<Leaderboard, ScoresError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Leaderboard::default()),
+            Err(error) => Err(ScoresError::Io(error)),
+        }
+    }
+
+    // Reads the leaderboard before changing it. A corrupt file is moved aside, under a name
+    // no earlier backup has taken, so that saving does not overwrite what was in it.
+    pub fn load(path: &Path) -> Result<(Leaderboard, LoadStatus), ScoresError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok((Leaderboard::default(), LoadStatus::Missing));
+            }
+            Err(error) => return Err(ScoresError::Io(error)),
+        };
+
+        match serde_json::from_str(&text) {
+            Ok(leaderboard) => Ok((leaderboard, LoadStatus::Loaded)),
+            Err(_) => {
+                let backup = backup_path(path);
+                fs::rename(path, &backup)?;
+                Ok((Leaderboard::default(), LoadStatus::Recovered { backup }))
+            }
+        }
+    }
+
+    // Writes to a temporary file next to the target and renames it into place,
+    // so a crash mid-write never leaves a half-written leaderboard behind.
+    pub fn save(&self, path: &Path) -> Result<(), ScoresError> {
+        let json = serde_json::to_string_pretty(self)?;
+        let tmp = sibling(path, ".tmp");
+
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    pub fn reset(path: &Path) -> Result<(), ScoresError> {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(ScoresError::Io(error)),
+            _ => Ok(()),
+        }
+    }
+
+    // Returns true when the score is a new personal best for that player and difficulty.
+    pub fn submit(&mut self, score: Score) -> bool {
+        let existing = self
+            .scores
+            .iter_mut()
+            .find(|s| s.player == score.player && s.difficulty == score.difficulty);
+
+        match existing {
+            Some(best) if score.rank(best) != Ordering::Less => false,
+            Some(best) => {
+                *best = score;
+                true
+            }
+            None => {
+                self.scores.push(score);
+                true
+            }
+        }
+    }
+
+    pub fn best(&self, player: &str, difficulty: Difficulty) -> Option<&Score> {
+        self.scores
+            .iter()
+            .find(|s| s.player == player && s.difficulty == difficulty)
+    }
+
+    pub fn top(&self, difficulty: Difficulty, n: usize) -> Vec<&Score> {
+        let mut scores: Vec<&Score> = self
+            .scores
+            .iter()
+            .filter(|s| s.difficulty == difficulty)
+            .collect();
+        scores.sort_by(|a, b| a.rank(b));
+        scores.truncate(n);
+        scores
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// "scores.json.corrupt", or "scores.json.corrupt.2" and so on if that is taken.
+fn backup_path(path: &Path) -> PathBuf {
+    std::iter::once(sibling(path, ".corrupt"))
+        .chain((2..).map(|n| sibling(path, &format!(".corrupt.{n}"))))
+        .find(|backup| !backup.exists())
+        .expect("the counter runs out long after the disk does")
+}
+
+#[derive(Debug)]
+pub enum ScoresError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl From<io::Error> for ScoresError {
+    fn from(error: io::Error) -> Self {
+        ScoresError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ScoresError {
+    fn from(error: serde_json::Error) -> Self {
+        ScoresError::Json(error)
+    }
+}
+
+impl fmt::Display for ScoresError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoresError::Io(error) => write!(f, "{error}"),
+            ScoresError::Json(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ScoresError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player: &str, difficulty: Difficulty, attempts: u32, elapsed_ms: u64) -> Score {
+        Score {
+            player: player.to_string(),
+            difficulty,
+            attempts,
+            elapsed_ms,
+            recorded_at: 0,
+        }
+    }
+
+    #[test]
+    fn keeps_only_personal_bests() {
+        let mut leaderboard = Leaderboard::default();
+
+        assert!(leaderboard.submit(score("ann", Difficulty::Hard, 8, 5000)));
+        assert!(!leaderboard.submit(score("ann", Difficulty::Hard, 9, 1000)));
+        assert!(leaderboard.submit(score("ann", Difficulty::Hard, 8, 3000)));
+        assert!(leaderboard.submit(score("ann", Difficulty::Easy, 20, 3000)));
+
+        assert_eq!(
+            leaderboard
+                .best("ann", Difficulty::Hard)
+                .unwrap()
+                .elapsed_ms,
+            3000
+        );
+        assert_eq!(leaderboard.top(Difficulty::Hard, 10).len(), 1);
+    }
+
+    #[test]
+    fn top_is_sorted_and_truncated() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.submit(score("ann", Difficulty::Normal, 7, 900));
+        leaderboard.submit(score("bob", Difficulty::Normal, 5, 4000));
+        leaderboard.submit(score("cat", Difficulty::Normal, 7, 100));
+
+        let names: Vec<&str> = leaderboard
+            .top(Difficulty::Normal, 2)
+            .iter()
+            .map(|s| s.player.as_str())
+            .collect();
+
+        assert_eq!(names, ["bob", "cat"]);
+    }
+
+    #[test]
+    fn save_then_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("round-trip.json");
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.submit(score("ann", Difficulty::Easy, 4, 1200));
+
+        leaderboard.save(&path).unwrap();
+        let (loaded, status) = Leaderboard::load(&path).unwrap();
+
+        assert_eq!(status, LoadStatus::Loaded);
+        assert_eq!(loaded, leaderboard);
+        assert!(!sibling(&path, ".tmp").exists());
+
+        Leaderboard::reset(&path).unwrap();
+        assert_eq!(Leaderboard::load(&path).unwrap().1, LoadStatus::Missing);
+    }
+
+    #[test]
+    fn recovers_from_corrupted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corrupt.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        let (leaderboard, status) = Leaderboard::load(&path).unwrap();
+
+        assert!(leaderboard.is_empty());
+        assert_eq!(
+            status,
+            LoadStatus::Recovered {
+                backup: sibling(&path, ".corrupt")
+            }
+        );
+        assert!(!path.exists());
+
+        // A second corrupt file keeps the first backup.
+        fs::write(&path, "also not json").unwrap();
+        let (_, status) = Leaderboard::load(&path).unwrap();
+        assert_eq!(
+            status,
+            LoadStatus::Recovered {
+                backup: sibling(&path, ".corrupt.2")
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(sibling(&path, ".corrupt")).unwrap(),
+            "{ not json"
+        );
+    }
+
+    #[test]
+    fn read_leaves_a_corrupt_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("read-only.json");
+        assert!(Leaderboard::read(&path).unwrap().is_empty());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(
+            Leaderboard::read(&path),
+            Err(ScoresError::Json(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+        assert!(!sibling(&path, ".corrupt").exists());
+    }
+}
@@ -0,0 +1,217 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+
+use crate::{
+    config::GameConfig,
+    game::{Game, GuessError, GuessOutcome},
+};
+
+// A Strategy plays the game the same way a person does:
+// it proposes a guess, then hears back the `Ordering` of that guess against the secret.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    // Called once before every game so the strategy can forget what it learned last time.
+    fn start(&mut self, config: &GameConfig);
+
+    fn next_guess(&mut self) -> i32;
+
+    fn observe(&mut self, guess: i32, feedback: Ordering);
+}
+
+pub const STRATEGY_NAMES: [&str; 3] = ["binary", "random", "linear"];
+
+// `seed` only matters for strategies that make random choices.
+pub fn by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "binary" => Some(Box::new(BinarySearch::default())),
+        "random" => Some(Box::new(RandomGuess::new(seed))),
+        "linear" => Some(Box::new(Linear::default())),
+        _ => None,
+    }
+}
+
+// Lets a strategy play a game to the end and returns the final outcome.
+pub fn solve(game: &mut Game, strategy: &mut dyn Strategy) -> Result<GuessOutcome, GuessError> {
+    strategy.start(game.config());
+
+    loop {
+        let guess = strategy.next_guess();
+        let outcome = game.guess(guess)?;
+        if let Some(feedback) = outcome.ordering() {
+            strategy.observe(guess, feedback);
+        }
+        if outcome.is_finished() {
+            return Ok(outcome);
+        }
+    }
+}
+
+// Always guesses the middle of the numbers that are still possible.
+#[derive(Debug, Clone, Default)]
+pub struct BinarySearch {
+    low: i32,
+    high: i32,
+}
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn start(&mut self, config: &GameConfig) {
+        self.low = config.min();
+        self.high = config.max();
+    }
+
+    fn next_guess(&mut self) -> i32 {
+        // Computed in i64 so that ranges near i32::MIN/MAX can't overflow.
+        ((self.low as i64 + self.high as i64).div_euclid(2)) as i32
+    }
+
+    fn observe(&mut self, guess: i32, feedback: Ordering) {
+        narrow(&mut self.low, &mut self.high, guess, feedback);
+    }
+}
+
+// Picks any number that is still possible, uniformly at random.
+#[derive(Debug, Clone)]
+pub struct RandomGuess {
+    rng: ChaCha8Rng,
+    low: i32,
+    high: i32,
+}
+
+impl RandomGuess {
+    pub fn new(seed: u64) -> RandomGuess {
+        RandomGuess {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            low: 0,
+            high: 0,
+        }
+    }
+}
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn start(&mut self, config: &GameConfig) {
+        self.low = config.min();
+        self.high = config.max();
+    }
+
+    fn next_guess(&mut self) -> i32 {
+        self.rng.random_range(self.low..=self.high)
+    }
+
+    fn observe(&mut self, guess: i32, feedback: Ordering) {
+        narrow(&mut self.low, &mut self.high, guess, feedback);
+    }
+}
+
+// Counts up from the lowest number and ignores the hints, the baseline every other strategy should beat.
+#[derive(Debug, Clone, Default)]
+pub struct Linear {
+    next: i32,
+}
+
+impl Strategy for Linear {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn start(&mut self, config: &GameConfig) {
+        self.next = config.min();
+    }
+
+    fn next_guess(&mut self) -> i32 {
+        self.next
+    }
+
+    // Only its own guesses move it along, hints about other numbers are ignored.
+    fn observe(&mut self, guess: i32, _feedback: Ordering) {
+        if guess == self.next {
+            self.next = guess.saturating_add(1);
+        }
+    }
+}
+
+// Only ever tightens the window. In a multiplayer game the hints of other players arrive too,
+// some of them about numbers this strategy has already ruled out, and those tell it nothing.
+fn narrow(low: &mut i32, high: &mut i32, guess: i32, feedback: Ordering) {
+    if guess < *low || guess > *high {
+        return;
+    }
+    match feedback {
+        Ordering::Less => *low = (*low).max(guess.saturating_add(1)),
+        Ordering::Greater => *high = (*high).min(guess.saturating_sub(1)),
+        Ordering::Equal => {
+            *low = guess;
+            *high = guess;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_strategy_finds_every_secret() {
+        let config = GameConfig::new(-5, 20, None).unwrap();
+
+        for name in STRATEGY_NAMES {
+            let mut strategy = by_name(name, 1).unwrap();
+            for secret in config.range() {
+                let mut game = Game::with_secret(config.clone(), secret).unwrap();
+                let outcome = solve(&mut game, strategy.as_mut()).unwrap();
+                assert!(
+                    matches!(outcome, GuessOutcome::Won { .. }),
+                    "{name} missed {secret}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn binary_search_handles_extreme_ranges() {
+        let config = GameConfig::new(i32::MIN, i32::MAX, None).unwrap();
+
+        for secret in [i32::MIN, -1, 0, i32::MAX] {
+            let mut game = Game::with_secret(config.clone(), secret).unwrap();
+            let outcome = solve(&mut game, &mut BinarySearch::default()).unwrap();
+            assert!(matches!(outcome, GuessOutcome::Won { attempts } if attempts <= 33));
+        }
+    }
+
+    #[test]
+    fn hints_from_several_players_only_narrow() {
+        let config = GameConfig::new(1, 100, None).unwrap();
+        let mut binary = BinarySearch::default();
+        let mut random = RandomGuess::new(7);
+        for strategy in [&mut binary as &mut dyn Strategy, &mut random] {
+            strategy.start(&config);
+            // Its own guess, then late hints from two other players about older guesses.
+            strategy.observe(50, Ordering::Less);
+            strategy.observe(10, Ordering::Less);
+            strategy.observe(90, Ordering::Greater);
+            strategy.observe(95, Ordering::Greater);
+            strategy.observe(40, Ordering::Less);
+        }
+
+        assert_eq!((binary.low, binary.high), (51, 89));
+        assert_eq!(binary.next_guess(), 70);
+        assert_eq!((random.low, random.high), (51, 89));
+        for _ in 0..100 {
+            assert!((51..=89).contains(&random.next_guess()));
+        }
+    }
+
+    #[test]
+    fn unknown_strategy_name() {
+        assert!(by_name("psychic", 0).is_none());
+    }
+}
@@ -0,0 +1,321 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    config::{ConfigError, GameConfig},
+    game::{Game, GuessError, GuessOutcome},
+};
+
+// A transcript is everything needed to reproduce a session: the seed, the config and every accepted guess.
+// It is stored as plain text, one fact per line, so bug reports can paste it directly:
+//
+//   # guessing game transcript
+//   seed 42
+//   range 1 100
+//   attempts 10
+//   guess 50 too-small 9
+//   guess 75 too-big 8
+//   guess 62 won 3
+//
+// Rejected guesses (out of range, not a number) never reach the Game, so they are not recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub seed: u64,
+    pub config: GameConfig,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub guess: i32,
+    pub outcome: GuessOutcome,
+}
+
+impl Transcript {
+    pub fn new(seed: u64, config: GameConfig) -> Transcript {
+        Transcript {
+            seed,
+            config,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, guess: i32, outcome: GuessOutcome) {
+        self.entries.push(Entry { guess, outcome });
+    }
+
+    // Builds a fresh game from the seed and checks that every guess still produces the recorded outcome.
+    // On success the finished game is returned so callers can inspect it.
+    pub fn replay(&self) -> Result<Game, ReplayError> {
+        let mut game = Game::from_seed(self.config.clone(), self.seed);
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let actual = game
+                .guess(entry.guess)
+                .map_err(|error| ReplayError::Rejected { index, error })?;
+
+            if actual != entry.outcome {
+                return Err(ReplayError::Mismatch {
+                    index,
+                    guess: entry.guess,
+                    expected: entry.outcome,
+                    actual,
+                });
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# guessing game transcript")?;
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "range {} {}", self.config.min(), self.config.max())?;
+        match self.config.max_attempts() {
+            Some(max_attempts) => writeln!(f, "attempts {max_attempts}")?,
+            None => writeln!(f, "attempts unlimited")?,
+        }
+
+        for entry in &self.entries {
+            write!(f, "guess {} ", entry.guess)?;
+            match entry.outcome {
+                GuessOutcome::TooSmall { attempts_left } => {
+                    write!(f, "too-small")?;
+                    write_attempts_left(f, attempts_left)?;
+                }
+                GuessOutcome::TooBig { attempts_left } => {
+                    write!(f, "too-big")?;
+                    write_attempts_left(f, attempts_left)?;
+                }
+                GuessOutcome::Won { attempts } => write!(f, "won {attempts}")?,
+                GuessOutcome::Lost { secret } => write!(f, "lost {secret}")?,
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_attempts_left(f: &mut fmt::Formatter<'_>, attempts_left: Option<u32>) -> fmt::Result {
+    match attempts_left {
+        Some(attempts_left) => write!(f, " {attempts_left}"),
+        None => Ok(()),
+    }
+}
+
+impl FromStr for Transcript {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seed = None;
+        let mut range = None;
+        let mut attempts = None;
+        let mut entries = Vec::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || ParseError::InvalidLine {
+                line_number,
+                line: line.to_string(),
+            };
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            match parts.as_slice() {
+                ["seed", value] => seed = Some(value.parse().map_err(|_| invalid())?),
+                ["range", min, max] => {
+                    let min = min.parse().map_err(|_| invalid())?;
+                    let max = max.parse().map_err(|_| invalid())?;
+                    range = Some((min, max));
+                }
+                ["attempts", "unlimited"] => attempts = Some(None),
+                ["attempts", value] => attempts = Some(Some(value.parse().map_err(|_| invalid())?)),
+                ["guess", guess, outcome @ ..] => {
+                    let guess = guess.parse().map_err(|_| invalid())?;
+                    let outcome = parse_outcome(outcome).ok_or_else(invalid)?;
+                    entries.push(Entry { guess, outcome });
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        let seed = seed.ok_or(ParseError::MissingField("seed"))?;
+        let (min, max) = range.ok_or(ParseError::MissingField("range"))?;
+        let attempts = attempts.ok_or(ParseError::MissingField("attempts"))?;
+        let config = GameConfig::new(min, max, attempts)?;
+
+        Ok(Transcript {
+            seed,
+            config,
+            entries,
+        })
+    }
+}
+
+fn parse_outcome(parts: &[&str]) -> Option<GuessOutcome> {
+    let outcome = match parts {
+        ["too-small"] => GuessOutcome::TooSmall {
+            attempts_left: None,
+        },
+        ["too-small", left] => GuessOutcome::TooSmall {
+            attempts_left: Some(left.parse().ok()?),
+        },
+        ["too-big"] => GuessOutcome::TooBig {
+            attempts_left: None,
+        },
+        ["too-big", left] => GuessOutcome::TooBig {
+            attempts_left: Some(left.parse().ok()?),
+        },
+        ["won", attempts] => GuessOutcome::Won {
+            attempts: attempts.parse().ok()?,
+        },
+        ["lost", secret] => GuessOutcome::Lost {
+            secret: secret.parse().ok()?,
+        },
+        _ => return None,
+    };
+
+    Some(outcome)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidLine { line_number: usize, line: String },
+    MissingField(&'static str),
+    Config(ConfigError),
+}
+
+impl From<ConfigError> for ParseError {
+    fn from(error: ConfigError) -> Self {
+        ParseError::Config(error)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidLine { line_number, line } => {
+                write!(f, "line {line_number}: cannot parse '{line}'")
+            }
+            ParseError::MissingField(field) => write!(f, "transcript has no '{field}' line"),
+            ParseError::Config(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    Rejected {
+        index: usize,
+        error: GuessError,
+    },
+    Mismatch {
+        index: usize,
+        guess: i32,
+        expected: GuessOutcome,
+        actual: GuessOutcome,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Rejected { index, error } => {
+                write!(f, "guess #{} was rejected: {error}", index + 1)
+            }
+            ReplayError::Mismatch {
+                index,
+                guess,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "guess #{} ({guess}) was recorded as {expected:?} but replayed as {actual:?}",
+                index + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    // Plays a game by binary search and records every step.
+    fn record(seed: u64, config: GameConfig) -> Transcript {
+        let mut game = Game::from_seed(config.clone(), seed);
+        let mut transcript = Transcript::new(seed, config.clone());
+        let (mut low, mut high) = (config.min(), config.max());
+
+        loop {
+            let guess = low + (high - low) / 2;
+            let outcome = game.guess(guess).unwrap();
+            transcript.record(guess, outcome);
+
+            match outcome {
+                GuessOutcome::TooSmall { .. } => low = guess + 1,
+                GuessOutcome::TooBig { .. } => high = guess - 1,
+                GuessOutcome::Won { .. } | GuessOutcome::Lost { .. } => return transcript,
+            }
+        }
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let transcript = record(7, GameConfig::new(1, 1000, Some(3)).unwrap());
+        let parsed: Transcript = transcript.to_string().parse().unwrap();
+
+        assert_eq!(parsed, transcript);
+    }
+
+    #[test]
+    fn replays_recorded_sessions() {
+        for seed in 0..20 {
+            let transcript = record(seed, GameConfig::new(1, 100, None).unwrap());
+            let game = transcript.replay().unwrap();
+            assert_eq!(game.state(), GameState::Won);
+        }
+    }
+
+    #[test]
+    fn detects_tampered_outcome() {
+        let mut transcript = record(3, GameConfig::new(1, 100, None).unwrap());
+        let last = transcript.entries.len() - 1;
+        transcript.entries[last].outcome = GuessOutcome::TooBig {
+            attempts_left: None,
+        };
+
+        assert!(matches!(
+            transcript.replay(),
+            Err(ReplayError::Mismatch { index, .. }) if index == last
+        ));
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        let text = "seed 1\nrange 1 10\nattempts 3\nguess five too-small\n";
+
+        assert_eq!(
+            text.parse::<Transcript>(),
+            Err(ParseError::InvalidLine {
+                line_number: 4,
+                line: "guess five too-small".to_string()
+            })
+        );
+        assert_eq!(
+            "range 1 10\nattempts 3\n".parse::<Transcript>(),
+            Err(ParseError::MissingField("seed"))
+        );
+    }
+}
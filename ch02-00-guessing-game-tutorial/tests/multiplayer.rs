@@ -0,0 +1,128 @@
+use ch02_00_guessing_game_tutorial::{
+    config::GameConfig,
+    game::Game,
+    net::{
+        client::Client,
+        protocol::{ClientMessage, ServerMessage},
+        server::{Server, Summary},
+    },
+};
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+fn start_server(game: Game) -> (String, thread::JoinHandle<Summary>) {
+    let server = Server::bind("127.0.0.1:0", game).unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    let handle = thread::spawn(move || server.run().unwrap());
+    (addr, handle)
+}
+
+#[test]
+fn several_client_processes_take_turns_until_someone_wins() {
+    let config = GameConfig::new(1, 1000, None).unwrap();
+    let (addr, server) = start_server(Game::from_seed(config, 2024));
+
+    let clients: Vec<_> = ["binary", "random", "linear"]
+        .iter()
+        .map(|strategy| {
+            Command::new(env!("CARGO_BIN_EXE_guessing-client"))
+                .args(["--addr", &addr, "--strategy", strategy, "--seed", "5"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+
+    let summary = server.join().unwrap();
+    let outputs: Vec<String> = clients
+        .into_iter()
+        .map(|client| {
+            let output = client.wait_with_output().unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap()
+        })
+        .collect();
+
+    let winner = summary
+        .winner
+        .expect("an unlimited game always has a winner");
+    let winners = outputs
+        .iter()
+        .filter(|out| out.contains("You win!"))
+        .count();
+    assert_eq!(winners, 1);
+    assert!(outputs.iter().all(|out| out.contains("WELCOME")));
+    assert!(
+        outputs
+            .iter()
+            .filter(|out| !out.contains("You win!"))
+            .all(|out| out.contains(&format!("OVER {winner} {}", summary.secret)))
+    );
+}
+
+#[test]
+fn rejects_guesses_out_of_turn_and_out_of_range() {
+    let config = GameConfig::new(1, 10, Some(3)).unwrap();
+    let (addr, server) = start_server(Game::with_secret(config, 7).unwrap());
+
+    let mut first = Client::connect(&addr).unwrap();
+    assert_eq!(
+        first.recv().unwrap(),
+        Some(ServerMessage::Welcome {
+            player: 1,
+            min: 1,
+            max: 10
+        })
+    );
+    assert_eq!(first.recv().unwrap(), Some(ServerMessage::Turn));
+
+    let mut second = Client::connect(&addr).unwrap();
+    assert!(matches!(
+        second.recv().unwrap(),
+        Some(ServerMessage::Welcome { player: 2, .. })
+    ));
+
+    second.send(ClientMessage::Guess(5)).unwrap();
+    assert_eq!(
+        second.recv().unwrap(),
+        Some(ServerMessage::Error("not your turn".to_string()))
+    );
+
+    first.send(ClientMessage::Guess(11)).unwrap();
+    assert!(matches!(
+        first.recv().unwrap(),
+        Some(ServerMessage::Error(_))
+    ));
+    assert_eq!(first.recv().unwrap(), Some(ServerMessage::Turn));
+
+    first.send(ClientMessage::Guess(2)).unwrap();
+    assert!(matches!(
+        first.recv().unwrap(),
+        Some(ServerMessage::Hint(_))
+    ));
+    assert!(matches!(
+        second.recv().unwrap(),
+        Some(ServerMessage::Played {
+            player: 1,
+            guess: 2,
+            ..
+        })
+    ));
+    assert_eq!(second.recv().unwrap(), Some(ServerMessage::Turn));
+
+    second.send(ClientMessage::Guess(7)).unwrap();
+    assert_eq!(second.recv().unwrap(), Some(ServerMessage::Win));
+    assert_eq!(
+        first.recv().unwrap(),
+        Some(ServerMessage::Over {
+            winner: Some(2),
+            secret: 7
+        })
+    );
+
+    let summary = server.join().unwrap();
+    assert_eq!(summary.winner, Some(2));
+    assert_eq!(summary.attempts, 2);
+}
@@ -0,0 +1,126 @@
+// Compares `SpatialIndex` with checking every rectangle, which is what it replaces.
+//
+//     cargo bench --bench spatial_index
+use std::hint::black_box;
+
+use ch05_03_method_syntax::{
+    geometry::{Point, Rect},
+    spatial_index::SpatialIndex,
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+// Rectangles up to 50 units a side, spread so that each overlaps a few others whatever
+// their number. A fixed xorshift sequence keeps every run on the same rectangles.
+fn rects(count: usize) -> Vec<Rect> {
+    let side = (count as f64).sqrt() as u32 * 40 + 100;
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = move |below: u32| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % u64::from(below)) as u32
+    };
+    (0..count)
+        .map(|_| Rect::new(next(side), next(side), next(50) + 1, next(50) + 1).unwrap())
+        .collect()
+}
+
+fn scan_window(rects: &[Rect], window: &Rect) -> usize {
+    rects.iter().filter(|rect| rect.intersects(window)).count()
+}
+
+fn scan_point(rects: &[Rect], point: Point) -> usize {
+    rects
+        .iter()
+        .filter(|rect| rect.contains_point(point))
+        .count()
+}
+
+fn scan_nearest(rects: &[Rect], point: Point) -> Option<&Rect> {
+    rects.iter().min_by_key(|rect| {
+        let dx = u64::from(rect.x().saturating_sub(point.x) + point.x.saturating_sub(rect.right()));
+        let dy =
+            u64::from(rect.y().saturating_sub(point.y) + point.y.saturating_sub(rect.bottom()));
+        dx * dx + dy * dy
+    })
+}
+
+fn scan_pairs(rects: &[Rect]) -> usize {
+    let mut pairs = 0;
+    for (i, rect) in rects.iter().enumerate() {
+        pairs += rects[i + 1..]
+            .iter()
+            .filter(|other| rect.intersects(other))
+            .count();
+    }
+    pairs
+}
+
+// Runs the same query through the index and through a scan, at each size.
+fn compare(
+    c: &mut Criterion,
+    name: &str,
+    index_query: impl Fn(&SpatialIndex<()>) -> usize,
+    scan: impl Fn(&[Rect]) -> usize,
+) {
+    let mut group = c.benchmark_group(name);
+    for count in SIZES {
+        let rects = rects(count);
+        let index: SpatialIndex<()> = rects.iter().map(|&rect| (rect, ())).collect();
+        group.bench_with_input(BenchmarkId::new("index", count), &index, |b, index| {
+            b.iter(|| index_query(index))
+        });
+        group.bench_with_input(BenchmarkId::new("scan", count), &rects, |b, rects| {
+            b.iter(|| scan(rects))
+        });
+    }
+    group.finish();
+}
+
+fn queries(c: &mut Criterion) {
+    let window = Rect::new(200, 200, 100, 100).unwrap();
+    let point = Point::new(250, 250);
+
+    compare(
+        c,
+        "window",
+        |index| index.query(black_box(&window)).len(),
+        |rects| scan_window(rects, black_box(&window)),
+    );
+    compare(
+        c,
+        "point",
+        |index| index.at_point(black_box(point)).len(),
+        |rects| scan_point(rects, black_box(point)),
+    );
+    compare(
+        c,
+        "nearest",
+        |index| index.nearest(black_box(point), 1).len(),
+        |rects| usize::from(scan_nearest(rects, black_box(point)).is_some()),
+    );
+}
+
+// Every overlapping pair, including the time to build the index, since that is what a
+// one-off check costs.
+fn overlapping_pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("overlapping_pairs");
+    for count in SIZES {
+        let rects = rects(count);
+        group.bench_with_input(BenchmarkId::new("index", count), &rects, |b, rects| {
+            b.iter(|| {
+                let index: SpatialIndex<()> = rects.iter().map(|&rect| (rect, ())).collect();
+                index.overlapping_pairs().len()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("scan", count), &rects, |b, rects| {
+            b.iter(|| scan_pairs(rects))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, queries, overlapping_pairs);
+criterion_main!(benches);
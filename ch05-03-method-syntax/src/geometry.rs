@@ -0,0 +1,9 @@
+pub mod rect;
+pub mod shape;
+
+pub use rect::{Point, Rect};
+pub use shape::{Circle, Outline, Polygon, Shape, ShapeError, Square, Triangle};
+
+// Coordinates are whole units on a grid whose origin is the top-left corner: x grows to the
+// right and y grows downwards, as on a screen. Sizes and positions are u32, and every
+// operation that could leave that range is checked and returns an Option instead.
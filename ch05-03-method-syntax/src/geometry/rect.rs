@@ -0,0 +1,330 @@
+use std::fmt;
+
+use crate::rectangle::Rectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Point {
+    pub fn new(x: u32, y: u32) -> Point {
+        Point { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// A `Rectangle` with a position: `width` by `height` units with its top-left corner at
+// (`x`, `y`). It covers the half-open ranges x..x + width and y..y + height, so two
+// rectangles that only share an edge do not overlap, and a point on the right or bottom
+// edge is outside.
+//
+// The fields are private so that the right and bottom edges always fit in a u32; the
+// constructors and every method that could push them further return None instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Rect {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Option<Rect> {
+        x.checked_add(width)?;
+        y.checked_add(height)?;
+        Some(Rect {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    // A `Rectangle` placed with its top-left corner at `origin`.
+    pub fn at(origin: Point, size: &Rectangle) -> Option<Rect> {
+        Rect::new(origin.x, origin.y, size.width, size.height)
+    }
+
+    // The rectangle spanning two opposite corners, given in any order.
+    pub fn from_corners(a: Point, b: Point) -> Rect {
+        let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+        let (top, bottom) = (a.y.min(b.y), a.y.max(b.y));
+        Rect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        }
+    }
+
+    // The smallest rectangle around all of `rects`, or None if there are none.
+    pub fn bounding_box(rects: impl IntoIterator<Item = Rect>) -> Option<Rect> {
+        rects.into_iter().reduce(|a, b| a.union(&b))
+    }
+
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn origin(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    // The first x past the rectangle.
+    pub fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    // The first y past the rectangle.
+    pub fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    pub fn size(&self) -> Rectangle {
+        Rectangle {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // A u64, which holds the area of any rectangle a u32 can describe.
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    // A rectangle with no width or no height covers nothing: it contains no points and
+    // intersects nothing, though it still has a position.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        (self.x..self.right()).contains(&point.x) && (self.y..self.bottom()).contains(&point.y)
+    }
+
+    // Whether `other` lies entirely inside this rectangle; it may touch the edges. Unlike
+    // `Rectangle::can_hold`, this looks at where the rectangles are, not just their sizes.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    // Whether the rectangles share some area. Sharing only an edge or a corner does not count.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // The area both rectangles cover, or None if they do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        (left < right && top < bottom).then(|| Rect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+
+    // The smallest rectangle covering both. It always fits, since both rectangles do.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::from_corners(
+            Point::new(self.x.min(other.x), self.y.min(other.y)),
+            Point::new(
+                self.right().max(other.right()),
+                self.bottom().max(other.bottom()),
+            ),
+        )
+    }
+
+    // The same rectangle moved by (`dx`, `dy`), or None if it would leave the grid.
+    pub fn translate(&self, dx: i32, dy: i32) -> Option<Rect> {
+        Rect::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+            self.width,
+            self.height,
+        )
+    }
+
+    // The same rectangle with its top-left corner at `origin`.
+    pub fn moved_to(&self, origin: Point) -> Option<Rect> {
+        Rect::new(origin.x, origin.y, self.width, self.height)
+    }
+
+    // Scales the whole picture by `factor` around the origin, so the position grows along
+    // with the size and rectangles keep their places relative to each other.
+    pub fn scale(&self, factor: u32) -> Option<Rect> {
+        Rect::new(
+            self.x.checked_mul(factor)?,
+            self.y.checked_mul(factor)?,
+            self.width.checked_mul(factor)?,
+            self.height.checked_mul(factor)?,
+        )
+    }
+}
+
+impl fmt::Display for Rect {
+    // "30x50 at (10, 20)"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} at {}", self.width, self.height, self.origin())
+    }
+}
+
+// A `Rectangle` has no position, so it becomes a `Rect` at the origin.
+impl From<Rectangle> for Rect {
+    fn from(rectangle: Rectangle) -> Self {
+        Rect {
+            x: 0,
+            y: 0,
+            width: rectangle.width,
+            height: rectangle.height,
+        }
+    }
+}
+
+// Keeps the size and drops the position.
+impl From<Rect> for Rectangle {
+    fn from(rect: Rect) -> Self {
+        rect.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rect {
+        Rect::new(x, y, width, height).unwrap()
+    }
+
+    #[test]
+    fn edges_are_checked() {
+        assert_eq!(
+            Rect::new(u32::MAX - 10, 0, 10, 5).unwrap().right(),
+            u32::MAX
+        );
+        assert_eq!(Rect::new(u32::MAX - 10, 0, 11, 5), None);
+        assert_eq!(Rect::new(0, 1, 3, u32::MAX), None);
+        assert_eq!(
+            Rect::at(Point::new(2, 3), &Rectangle::square(4)),
+            Some(rect(2, 3, 4, 4))
+        );
+        assert_eq!(
+            Rect::from_corners(Point::new(5, 1), Point::new(2, 7)),
+            rect(2, 1, 3, 6)
+        );
+        assert_eq!(
+            rect(1, 2, u32::MAX - 1, u32::MAX - 2).area(),
+            0xffff_fffb_0000_0006
+        );
+        assert_eq!(rect(10, 20, 30, 50).to_string(), "30x50 at (10, 20)");
+    }
+
+    #[test]
+    fn points_on_the_far_edges_are_outside() {
+        let r = rect(10, 10, 5, 5);
+
+        assert!(r.contains_point(Point::new(10, 10)));
+        assert!(r.contains_point(Point::new(14, 14)));
+        assert!(!r.contains_point(Point::new(15, 12)));
+        assert!(!r.contains_point(Point::new(12, 15)));
+        assert!(!r.contains_point(Point::new(9, 12)));
+        assert!(!rect(3, 3, 0, 4).contains_point(Point::new(3, 3)));
+    }
+
+    #[test]
+    fn containment_allows_touching_edges() {
+        let outer = rect(0, 0, 10, 10);
+
+        assert!(outer.contains_rect(&outer));
+        assert!(outer.contains_rect(&rect(5, 5, 5, 5)));
+        assert!(!outer.contains_rect(&rect(5, 5, 6, 5)));
+        assert!(!rect(5, 5, 5, 5).contains_rect(&outer));
+    }
+
+    #[test]
+    fn intersection_needs_shared_area() {
+        let a = rect(0, 0, 10, 10);
+
+        assert_eq!(a.intersection(&rect(5, 8, 10, 10)), Some(rect(5, 8, 5, 2)));
+        assert_eq!(a.intersection(&rect(2, 2, 3, 3)), Some(rect(2, 2, 3, 3)));
+        assert!(a.intersects(&rect(9, 9, 1, 1)));
+        // Sharing an edge or a corner is not overlapping.
+        assert!(!a.intersects(&rect(10, 0, 5, 5)));
+        assert!(!a.intersects(&rect(10, 10, 5, 5)));
+        assert!(!a.intersects(&rect(4, 4, 0, 2)));
+        assert!(!a.intersects(&rect(20, 20, 1, 1)));
+    }
+
+    #[test]
+    fn union_is_the_bounding_box() {
+        let a = rect(0, 5, 2, 2);
+        let b = rect(10, 0, 3, 1);
+
+        assert_eq!(a.union(&b), rect(0, 0, 13, 7));
+        assert_eq!(a.union(&b), b.union(&a));
+        assert_eq!(
+            Rect::bounding_box([a, b, rect(4, 4, 1, 10)]),
+            Some(rect(0, 0, 13, 14))
+        );
+        assert_eq!(Rect::bounding_box([]), None);
+        let far = rect(u32::MAX - 1, u32::MAX - 1, 1, 1);
+        assert_eq!(a.union(&far).bottom(), u32::MAX);
+    }
+
+    #[test]
+    fn moving_and_scaling_stay_on_the_grid() {
+        let r = rect(10, 10, 5, 5);
+
+        assert_eq!(r.translate(-10, 3), Some(rect(0, 13, 5, 5)));
+        assert_eq!(r.translate(-11, 0), None);
+        assert_eq!(rect(0, u32::MAX - 6, 1, 5).translate(0, 2), None);
+        assert_eq!(r.moved_to(Point::new(1, 2)), Some(rect(1, 2, 5, 5)));
+        assert_eq!(r.scale(3), Some(rect(30, 30, 15, 15)));
+        assert_eq!(r.scale(0), Some(rect(0, 0, 0, 0)));
+        assert_eq!(r.scale(u32::MAX / 14), None);
+    }
+
+    #[test]
+    fn converts_to_and_from_rectangle() {
+        let rect = Rect::from(Rectangle {
+            width: 30,
+            height: 50,
+        });
+        assert_eq!(rect.origin(), Point::new(0, 0));
+
+        let moved = rect.translate(7, 7).unwrap();
+        assert_eq!(
+            Rectangle::from(moved),
+            Rectangle {
+                width: 30,
+                height: 50
+            }
+        );
+        assert!(Rectangle::from(moved).can_hold(&Rectangle::square(10)));
+    }
+}
@@ -0,0 +1,645 @@
+use std::{f64::consts::PI, fmt};
+
+use super::rect::{Point, Rect};
+use crate::rectangle::Rectangle;
+
+// What `Rectangle::area` and `Rectangle::can_hold` do, for any shape on the grid.
+//
+// Areas and perimeters are f64, since circles and slanted edges rarely come out whole.
+pub trait Shape: fmt::Debug {
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    // The smallest grid rectangle around the shape.
+    fn bounding_box(&self) -> Rect;
+
+    // Whether a `width` by `height` box fits strictly inside the shape once moved, without
+    // touching its edges, like `Rectangle::can_hold` asks of rectangles.
+    fn can_hold_box(&self, width: f64, height: f64) -> bool;
+
+    // The edge of the shape, for drawing it.
+    fn outline(&self) -> Outline;
+
+    // Whether `other` fits strictly inside this shape once moved, without rotating it.
+    // `other` is measured by its bounding box, so a circle in a triangle, say, only counts
+    // as fitting when its bounding box does.
+    fn can_hold(&self, other: &dyn Shape) -> bool {
+        let bounds = other.bounding_box();
+        self.can_hold_box(f64::from(bounds.width()), f64::from(bounds.height()))
+    }
+}
+
+// Every shape here is drawn as one of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outline {
+    Rect(Rect),
+    Circle { center: Point, radius: u32 },
+    // A convex polygon, with its vertices in order around the edge.
+    Polygon(Vec<Point>),
+}
+
+impl Outline {
+    // Whether a point between the grid lines is in the shape. A rectangle covers the same
+    // half-open ranges as `Rect`, while a circle or polygon includes its edge.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        match self {
+            Outline::Rect(rect) => {
+                (f64::from(rect.x())..f64::from(rect.right())).contains(&x)
+                    && (f64::from(rect.y())..f64::from(rect.bottom())).contains(&y)
+            }
+            Outline::Circle { center, radius } => {
+                (x - f64::from(center.x)).hypot(y - f64::from(center.y)) <= f64::from(*radius)
+            }
+            // Inside a convex polygon, every edge turns the same way towards the point.
+            Outline::Polygon(vertices) => {
+                let turns = vertices
+                    .iter()
+                    .zip(vertices.iter().cycle().skip(1))
+                    .map(|(a, b)| {
+                        let edge = vector(*a, *b);
+                        cross(edge, (x - f64::from(a.x), y - f64::from(a.y)))
+                    });
+                let (mut left, mut right) = (false, false);
+                for turn in turns {
+                    left |= turn > 0.0;
+                    right |= turn < 0.0;
+                }
+                !(left && right)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeError {
+    // Part of the shape would be left of or above the origin, or past u32::MAX.
+    OffGrid,
+    // A polygon needs at least three corners.
+    TooFewVertices(usize),
+    // The corners are all on one line, so the shape has no area.
+    Degenerate,
+    NotConvex,
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::OffGrid => write!(f, "the shape does not fit on the grid"),
+            ShapeError::TooFewVertices(count) => {
+                write!(f, "a polygon needs at least 3 vertices, got {count}")
+            }
+            ShapeError::Degenerate => write!(f, "the vertices are all on one line"),
+            ShapeError::NotConvex => write!(f, "the polygon is not convex"),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+// A box that fits in a rectangle has room to spare both ways.
+fn rect_can_hold_box(rect_width: u32, rect_height: u32, width: f64, height: f64) -> bool {
+    f64::from(rect_width) > width && f64::from(rect_height) > height
+}
+
+// The book's `Rectangle` has no position, so it sits at the origin.
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        f64::from(self.width) * f64::from(self.height)
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (f64::from(self.width) + f64::from(self.height))
+    }
+
+    fn bounding_box(&self) -> Rect {
+        Rect::from(self.clone())
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Rect(self.bounding_box())
+    }
+
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        rect_can_hold_box(self.width, self.height, width, height)
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> f64 {
+        Rect::area(self) as f64
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (f64::from(self.width()) + f64::from(self.height()))
+    }
+
+    fn bounding_box(&self) -> Rect {
+        *self
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Rect(*self)
+    }
+
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        rect_can_hold_box(self.width(), self.height(), width, height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Square {
+    rect: Rect,
+}
+
+impl Square {
+    pub fn new(origin: Point, side: u32) -> Result<Square, ShapeError> {
+        let rect = Rect::new(origin.x, origin.y, side, side).ok_or(ShapeError::OffGrid)?;
+        Ok(Square { rect })
+    }
+
+    pub fn side(&self) -> u32 {
+        self.rect.width()
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        Shape::area(&self.rect)
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.rect.perimeter()
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.rect
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Rect(self.rect)
+    }
+
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        self.rect.can_hold_box(width, height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Circle {
+    center: Point,
+    radius: u32,
+}
+
+impl Circle {
+    // The whole circle has to be on the grid, so the center is at least `radius` from the
+    // top and left edges.
+    pub fn new(center: Point, radius: u32) -> Result<Circle, ShapeError> {
+        let left = center.x.checked_sub(radius).ok_or(ShapeError::OffGrid)?;
+        let top = center.y.checked_sub(radius).ok_or(ShapeError::OffGrid)?;
+        let diameter = radius.checked_mul(2).ok_or(ShapeError::OffGrid)?;
+        Rect::new(left, top, diameter, diameter).ok_or(ShapeError::OffGrid)?;
+        Ok(Circle { center, radius })
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * f64::from(self.radius).powi(2)
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * f64::from(self.radius)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        let diameter = 2 * self.radius;
+        Rect::new(
+            self.center.x - self.radius,
+            self.center.y - self.radius,
+            diameter,
+            diameter,
+        )
+        .expect("checked in Circle::new")
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Circle {
+            center: self.center,
+            radius: self.radius,
+        }
+    }
+
+    // A box fits in a circle when its diagonal is shorter than the diameter.
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        let diameter = 2.0 * f64::from(self.radius);
+        width.hypot(height) < diameter
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Triangle {
+    polygon: Polygon,
+}
+
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Result<Triangle, ShapeError> {
+        Ok(Triangle {
+            polygon: Polygon::new(vec![a, b, c])?,
+        })
+    }
+
+    pub fn vertices(&self) -> [Point; 3] {
+        match self.polygon.vertices() {
+            &[a, b, c] => [a, b, c],
+            _ => unreachable!("a triangle has three vertices"),
+        }
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        self.polygon.area()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.polygon.perimeter()
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.polygon.bounding_box()
+    }
+
+    fn outline(&self) -> Outline {
+        self.polygon.outline()
+    }
+
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        self.polygon.can_hold_box(width, height)
+    }
+}
+
+// A convex polygon, with its vertices in order around the edge in either direction.
+// Keeping to convex polygons is what lets `can_hold` give exact answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+fn vector(from: Point, to: Point) -> (f64, f64) {
+    (
+        f64::from(to.x) - f64::from(from.x),
+        f64::from(to.y) - f64::from(from.y),
+    )
+}
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+impl Polygon {
+    // Corners in a straight line with their neighbours are allowed, but the polygon has to
+    // turn the same way at every other corner and go round exactly once.
+    pub fn new(vertices: Vec<Point>) -> Result<Polygon, ShapeError> {
+        if vertices.len() < 3 {
+            return Err(ShapeError::TooFewVertices(vertices.len()));
+        }
+
+        let polygon = Polygon { vertices };
+        if polygon.signed_area() == 0.0 {
+            return Err(ShapeError::Degenerate);
+        }
+
+        let mut left = false;
+        let mut right = false;
+        let mut turned = 0.0;
+        for ((a, b), (_, c)) in polygon.edges().zip(polygon.edges().cycle().skip(1)) {
+            let (incoming, outgoing) = (vector(a, b), vector(b, c));
+            let turn = cross(incoming, outgoing);
+            left |= turn > 0.0;
+            right |= turn < 0.0;
+            turned += turn.atan2(incoming.0 * outgoing.0 + incoming.1 * outgoing.1);
+        }
+        // A star turns one way throughout but goes round twice.
+        if (left && right) || (turned.abs() - 2.0 * PI).abs() > 1e-6 {
+            return Err(ShapeError::NotConvex);
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    // Each edge as its start and end, with the last edge closing the polygon.
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + Clone + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    // The shoelace formula. Positive when the vertices turn clockwise on the grid, whose y
+    // axis points down.
+    fn signed_area(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| f64::from(a.x) * f64::from(b.y) - f64::from(b.x) * f64::from(a.y))
+            .sum::<f64>()
+            / 2.0
+    }
+}
+
+impl Shape for Polygon {
+    fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = vector(a, b);
+                dx.hypot(dy)
+            })
+            .sum()
+    }
+
+    fn bounding_box(&self) -> Rect {
+        let (first, rest) = self.vertices.split_first().expect("at least 3 vertices");
+        let (min, max) = rest.iter().fold((*first, *first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+        Rect::from_corners(min, max)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.vertices.clone())
+    }
+
+    // Each edge keeps the box on its inner side, which for the box's top-left corner `t`
+    // means a·t >= b for some a and b. The box fits strictly when some `t` clears every edge
+    // by more than nothing: the best `t` clears all of them by the same distance, and lies
+    // where three of the edge constraints meet, so every triple of edges is tried.
+    fn can_hold_box(&self, width: f64, height: f64) -> bool {
+        let inside = self.signed_area().signum();
+        let corners = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)];
+        let constraints: Vec<((f64, f64), f64)> = self
+            .edges()
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| {
+                let edge = vector(a, b);
+                // The inside of the edge is where inside * cross(edge, p - a) > 0.
+                let normal = (-edge.1 * inside, edge.0 * inside);
+                let length = normal.0.hypot(normal.1);
+                let normal = (normal.0 / length, normal.1 / length);
+                let at_edge = normal.0 * f64::from(a.x) + normal.1 * f64::from(a.y);
+                let nearest_corner = corners
+                    .iter()
+                    .map(|corner| normal.0 * corner.0 + normal.1 * corner.1)
+                    .fold(f64::INFINITY, f64::min);
+                (normal, at_edge - nearest_corner)
+            })
+            .collect();
+
+        let clearance = |t: (f64, f64)| {
+            constraints
+                .iter()
+                .map(|(normal, bound)| normal.0 * t.0 + normal.1 * t.1 - bound)
+                .fold(f64::INFINITY, f64::min)
+        };
+        let mut best = f64::NEG_INFINITY;
+        for i in 0..constraints.len() {
+            for j in i + 1..constraints.len() {
+                for k in j + 1..constraints.len() {
+                    if let Some(t) =
+                        equal_clearance([constraints[i], constraints[j], constraints[k]])
+                    {
+                        best = best.max(clearance(t));
+                    }
+                }
+            }
+        }
+        best > 1e-9
+    }
+}
+
+// The point that is the same distance inside three edges, where each edge is given by its unit
+// normal and offset. Solves a·t - s = b for t and s by Cramer's rule.
+fn equal_clearance(edges: [((f64, f64), f64); 3]) -> Option<(f64, f64)> {
+    let det3 = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let rows = edges.map(|((ax, ay), _)| [ax, ay, -1.0]);
+    let bounds = edges.map(|(_, bound)| bound);
+    let det = det3(rows);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let with_column = |column: usize| {
+        let mut m = rows;
+        for (row, bound) in m.iter_mut().zip(bounds) {
+            row[column] = bound;
+        }
+        det3(m)
+    };
+    Some((with_column(0) / det, with_column(1) / det))
+}
+
+// Smallest first. Shapes with the same area keep their order.
+pub fn sort_by_area(shapes: &mut [Box<dyn Shape>]) {
+    shapes.sort_by(|a, b| a.area().total_cmp(&b.area()));
+}
+
+// The shapes `container` can hold, in their original order.
+pub fn fitting_in<'a>(container: &dyn Shape, shapes: &'a [Box<dyn Shape>]) -> Vec<&'a dyn Shape> {
+    shapes
+        .iter()
+        .map(Box::as_ref)
+        .filter(|shape| container.can_hold(*shape))
+        .collect()
+}
+
+// The shape with the largest area, if there are any.
+pub fn largest(shapes: &[Box<dyn Shape>]) -> Option<&dyn Shape> {
+    shapes
+        .iter()
+        .map(Box::as_ref)
+        .max_by(|a, b| a.area().total_cmp(&b.area()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: u32, y: u32) -> Point {
+        Point::new(x, y)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn areas_and_perimeters() {
+        let rectangle = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(Shape::area(&rectangle), f64::from(rectangle.area()));
+        assert_eq!(rectangle.perimeter(), 160.0);
+
+        let square = Square::new(p(1, 1), 4).unwrap();
+        assert_eq!((square.area(), square.perimeter()), (16.0, 16.0));
+
+        let circle = Circle::new(p(10, 10), 2).unwrap();
+        assert!(close(circle.area(), 4.0 * PI));
+        assert!(close(circle.perimeter(), 4.0 * PI));
+
+        let triangle = Triangle::new(p(0, 0), p(4, 0), p(0, 3)).unwrap();
+        assert_eq!((triangle.area(), triangle.perimeter()), (6.0, 12.0));
+
+        // Both directions round give the same area.
+        let hexagon = Polygon::new(vec![p(2, 0), p(4, 0), p(6, 2), p(4, 4), p(2, 4), p(0, 2)]);
+        let reversed = Polygon::new(vec![p(0, 2), p(2, 4), p(4, 4), p(6, 2), p(4, 0), p(2, 0)]);
+        assert_eq!(hexagon.unwrap().area(), 16.0);
+        assert_eq!(reversed.unwrap().area(), 16.0);
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let rect = |x, y, w, h| Rect::new(x, y, w, h).unwrap();
+
+        assert_eq!(Rectangle::square(3).bounding_box(), rect(0, 0, 3, 3));
+        assert_eq!(
+            Circle::new(p(5, 7), 3).unwrap().bounding_box(),
+            rect(2, 4, 6, 6)
+        );
+        assert_eq!(
+            Triangle::new(p(3, 9), p(8, 1), p(1, 4))
+                .unwrap()
+                .bounding_box(),
+            rect(1, 1, 7, 8)
+        );
+    }
+
+    #[test]
+    fn outlines_contain_their_insides() {
+        let rect = Rectangle::square(4).outline();
+        assert!(rect.contains(0.0, 3.5));
+        assert!(!rect.contains(4.0, 1.0));
+
+        let circle = Circle::new(p(5, 5), 3).unwrap().outline();
+        assert!(circle.contains(5.0, 8.0));
+        assert!(!circle.contains(2.5, 2.5));
+
+        // Either direction round.
+        for triangle in [
+            Triangle::new(p(0, 0), p(4, 0), p(0, 4)).unwrap(),
+            Triangle::new(p(0, 4), p(4, 0), p(0, 0)).unwrap(),
+        ] {
+            let outline = triangle.outline();
+            assert!(outline.contains(1.0, 1.0));
+            assert!(outline.contains(2.0, 2.0));
+            assert!(!outline.contains(2.5, 2.5));
+        }
+    }
+
+    #[test]
+    fn shapes_must_fit_on_the_grid_and_be_convex() {
+        assert_eq!(Circle::new(p(2, 5), 3), Err(ShapeError::OffGrid));
+        assert_eq!(Circle::new(p(u32::MAX - 1, 5), 3), Err(ShapeError::OffGrid));
+        assert_eq!(Square::new(p(u32::MAX, 0), 1), Err(ShapeError::OffGrid));
+        assert_eq!(
+            Triangle::new(p(0, 0), p(1, 1), p(2, 2)),
+            Err(ShapeError::Degenerate)
+        );
+        assert_eq!(
+            Polygon::new(vec![p(0, 0), p(1, 1)]),
+            Err(ShapeError::TooFewVertices(2))
+        );
+        let dented = vec![p(0, 0), p(4, 0), p(2, 1), p(4, 4), p(0, 4)];
+        assert_eq!(Polygon::new(dented), Err(ShapeError::NotConvex));
+        let star = vec![p(5, 0), p(8, 10), p(0, 4), p(10, 4), p(2, 10)];
+        assert_eq!(Polygon::new(star), Err(ShapeError::NotConvex));
+        // A corner on a straight edge is fine.
+        assert!(Polygon::new(vec![p(0, 0), p(2, 0), p(4, 0), p(4, 4)]).is_ok());
+    }
+
+    #[test]
+    fn can_hold_generalizes_rectangle() {
+        let big = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        let small = Rectangle {
+            width: 10,
+            height: 40,
+        };
+        assert_eq!(Shape::can_hold(&big, &small), big.can_hold(&small));
+        assert_eq!(Shape::can_hold(&small, &big), small.can_hold(&big));
+        assert!(!Shape::can_hold(&big, &big));
+
+        let circle = Circle::new(p(10, 10), 5).unwrap();
+        // A 6x8 box has a 10 unit diagonal, exactly the diameter.
+        assert!(circle.can_hold_box(5.0, 8.0));
+        assert!(!circle.can_hold_box(6.0, 8.0));
+        assert!(circle.can_hold(&Rectangle::square(7)));
+        assert!(!circle.can_hold(&Rectangle::square(8)));
+    }
+
+    #[test]
+    fn polygons_hold_boxes_exactly() {
+        // A right triangle with legs of 10 holds a w x h box when w + h < 10.
+        let triangle = Triangle::new(p(0, 0), p(10, 0), p(0, 10)).unwrap();
+        assert!(triangle.can_hold_box(4.9, 5.0));
+        assert!(!triangle.can_hold_box(5.0, 5.0));
+        assert!(triangle.can_hold_box(9.0, 0.9));
+        assert!(!triangle.can_hold_box(10.0, 0.1));
+
+        // A diamond of half-width 4 holds a square of side just under 4.
+        let diamond = Polygon::new(vec![p(4, 0), p(8, 4), p(4, 8), p(0, 4)]).unwrap();
+        assert!(diamond.can_hold_box(3.9, 3.9));
+        assert!(!diamond.can_hold_box(4.0, 4.0));
+        assert!(diamond.can_hold_box(7.0, 0.9));
+    }
+
+    #[test]
+    fn sorts_and_filters_mixed_shapes() {
+        let mut shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Circle::new(p(10, 10), 3).unwrap()),
+            Box::new(Rectangle {
+                width: 2,
+                height: 9,
+            }),
+            Box::new(Square::new(p(0, 0), 2).unwrap()),
+            Box::new(Triangle::new(p(0, 0), p(6, 0), p(0, 6)).unwrap()),
+        ];
+
+        sort_by_area(&mut shapes);
+        let areas: Vec<f64> = shapes.iter().map(|shape| shape.area().round()).collect();
+        assert_eq!(areas, [4.0, 18.0, 18.0, 28.0]);
+
+        let frame = Rect::new(0, 0, 7, 7).unwrap();
+        let fits: Vec<f64> = fitting_in(&frame, &shapes)
+            .iter()
+            .map(|shape| shape.area().round())
+            .collect();
+        assert_eq!(fits, [4.0, 18.0, 28.0]);
+
+        let largest = largest(&shapes).unwrap();
+        assert_eq!(largest.bounding_box(), Rect::new(7, 7, 6, 6).unwrap());
+        assert!(largest.can_hold(shapes[0].as_ref()));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod geometry;
+pub mod packing;
+pub mod rectangle;
+pub mod render;
+pub mod spatial_index;
@@ -1,66 +1,9 @@
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
-
-// Everything within this impl block will be associated with the Rectangle type.
-impl Rectangle {
-    // In the signature for area, we use &self instead of rectangle: &Rectangle.
-    // The &self is actually short for self: &Self
-
-    // Within an impl block, the type Self is an alias for the type that the impl block is for.
-    // Methods must have a parameter named self of type Self for their first parameter,
-    // so Rust lets you abbreviate this with only the name self in the first parameter spot.
-    // Note that we still need to use the & in front of the self shorthand to indicate that this method borrows the Self instance,
-    // just as we did in rectangle: &Rectangle.
-    // Methods can take ownership of self, borrow self immutably, as we’ve done here, or borrow self mutably,
-    // just as they can any other parameter.
-    fn area(&self) -> u32 {
-        self.width * self.height
-    }
-
-    // Use `&mut self` when the method needs to modify the instance.
-    fn double_the_width(&mut self) {
-        self.width = 2 * self.width;
-    }
-
-    //  Having a method that takes ownership of the instance by using just self as the first parameter is rare;
-    // this technique is usually used when the method transforms self into something else and
-    // you want to prevent the caller from using the original instance after the transformation.
-    fn double_the_width_with_move(mut self) {
-        self.width = 2 * self.width;
-    }
-
-    // Note that we can choose to give a method the same name as one of the struct’s fields.
-    fn width(&self) -> bool {
-        self.width > 0
-    }
-
-    // Methods can take multiple parameters that we add to the signature after the self parameter, and
-    // those parameters work just like parameters in functions.
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-
-    // We can define associated functions that don’t have self as their first parameter (and thus are not methods) because
-    // they don’t need an instance of the type to work with.
-    // To call this associated function, we use the :: syntax with the struct name;
-    // This function is namespaced by the struct.
-    fn square(length: u32) -> Self {
-        Self {
-            width: length,
-            height: length,
-        }
-    }
-}
-
-// Each struct is allowed to have multiple impl blocks
-impl Rectangle {
-    fn print(&self) {
-        println!("Width: {}, Height: {}", self.width, self.height);
-    }
-}
+use ch05_03_method_syntax::{
+    geometry::{Circle, Point, Rect, Shape, Triangle, shape},
+    packing::{Heuristic, Packer},
+    rectangle::Rectangle,
+    render::{Ascii, Color, Drawing},
+};
 
 fn main() {
     // Methods are similar to functions:
@@ -112,6 +55,7 @@ fn main() {
     // When you call a method with object.something(), Rust automatically adds in &, &mut, or * so that object matches the signature of the method.
     // In other words, the following are the same:
     rect1.can_hold(&rect2); // looks much cleaner
+    #[allow(clippy::needless_borrow)] // the explicit borrow is what this line shows
     (&rect1).can_hold(&rect2);
 
     // The fact that Rust makes borrowing implicit for
@@ -130,4 +74,79 @@ fn main() {
     // associated function) define behaviors that operate on struct instances via `self`.
     // Structs aren’t the only way to create custom types in Rust—enums provide another
     // powerful option for modeling variant-based data and behavior.
+
+    // `Rectangle` only knows its size. `geometry::Rect` adds a position, which is what
+    // questions such as "do these two overlap?" need.
+    let window = Rect::at(Point::new(10, 10), &rect1).expect("fits on the grid");
+    let icon = Rect::from(Rectangle::square(20))
+        .translate(25, 5)
+        .expect("fits on the grid");
+    println!("{window} and {icon} overlap: {}", window.intersects(&icon));
+    if let Some(overlap) = window.intersection(&icon) {
+        println!(
+            "They share {overlap}, and together span {}",
+            window.union(&icon)
+        );
+    }
+
+    // The `Shape` trait asks the same questions as `area` and `can_hold` of any shape,
+    // so different kinds of shape can share one list.
+    let mut shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Circle::new(Point::new(20, 20), 8).expect("fits on the grid")),
+        Box::new(Rectangle::square(3)),
+        Box::new(
+            Triangle::new(Point::new(0, 0), Point::new(12, 0), Point::new(0, 12))
+                .expect("not a straight line"),
+        ),
+    ];
+    shape::sort_by_area(&mut shapes);
+    for fits in shape::fitting_in(&rect1, &shapes) {
+        println!(
+            "rect1 can hold {fits:?}, with an area of {:.1}",
+            fits.area()
+        );
+    }
+
+    // Packing fits many `Rectangle`s into as few containers of one size as it can.
+    let sheet = Rectangle {
+        width: 100,
+        height: 60,
+    };
+    let parts = [
+        rect1,
+        rect2,
+        sqr1,
+        Rectangle::square(40),
+        Rectangle::square(40),
+    ];
+    let packer = Packer {
+        heuristic: Heuristic::MaxRects,
+        allow_rotation: true,
+    };
+    let packing = packer.pack(&sheet, &parts);
+    for placement in &packing.placements {
+        println!(
+            "Part {} goes on sheet {} at {}",
+            placement.item, placement.bin, placement.rect
+        );
+    }
+    println!(
+        "{} sheets, {:.1}% used",
+        packing.bins,
+        packing.total_utilization()
+    );
+
+    // `print` only gives the numbers; a `Drawing` shows where things are. `render::Svg`
+    // draws the same thing as an SVG document.
+    let mut drawing = Drawing::new();
+    drawing.add(&window).label("window").color(Color::Blue);
+    drawing.add(&icon).label("icon").color(Color::Red);
+    let ascii = Ascii {
+        scale: 0.25,
+        ..Ascii::new()
+    };
+    match ascii.render(&drawing) {
+        Ok(art) => print!("{art}"),
+        Err(error) => println!("cannot draw the shapes: {error}"),
+    }
 }
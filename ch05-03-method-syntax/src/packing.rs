@@ -0,0 +1,374 @@
+use crate::{geometry::Rect, rectangle::Rectangle};
+
+mod guillotine;
+mod maxrects;
+mod shelf;
+
+// Packs rectangles such as sprites or cut sheets into as few fixed-size containers ("bins")
+// as the chosen heuristic manages. Items go in largest first, each into the first bin with
+// room for it, and a new bin is started when none has room.
+//
+// Optimal packing is NP-hard, so these are the usual heuristics, from the quickest and
+// loosest to the slowest and tightest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heuristic {
+    // Rows of items, each row as tall as its first, tallest item.
+    Shelf,
+    // Each item is put in a corner of a free rectangle, and what is left is cut in two,
+    // the way a saw cuts a sheet edge to edge.
+    Guillotine,
+    // Keeps every largest free rectangle, even where they overlap, and puts each item where
+    // it leaves the least room on its shorter side.
+    MaxRects,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packer {
+    pub heuristic: Heuristic,
+    // Whether items may be turned a quarter turn to fit better.
+    pub allow_rotation: bool,
+}
+
+// Where one item ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    // The item's index in the slice given to `pack`.
+    pub item: usize,
+    pub bin: usize,
+    // Position and size in the bin, which is the item's width by height, or its height by
+    // width when `rotated`.
+    pub rect: Rect,
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packing {
+    pub container: Rectangle,
+    pub bins: usize,
+    // In the order the items were placed.
+    pub placements: Vec<Placement>,
+    // Items too big for an empty container even when turned, by index.
+    pub unplaced: Vec<usize>,
+}
+
+// One container being filled. Each heuristic keeps its own record of the free space.
+trait Bin {
+    fn new(width: u32, height: u32) -> Self;
+
+    // Finds room for a `width` by `height` item, turned if `allow_rotation` and that fits
+    // better, and marks it as taken. Returns where the item went and whether it was turned.
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rect, bool)>;
+}
+
+// The ways an item can be put down: as it is, and turned if that is allowed and makes a
+// difference. Each is the width, the height and whether it is turned.
+fn orientations(width: u32, height: u32, allow_rotation: bool) -> Vec<(u32, u32, bool)> {
+    let mut orientations = vec![(width, height, false)];
+    if allow_rotation && width != height {
+        orientations.push((height, width, true));
+    }
+    orientations
+}
+
+impl Packer {
+    pub fn new(heuristic: Heuristic) -> Packer {
+        Packer {
+            heuristic,
+            allow_rotation: false,
+        }
+    }
+
+    pub fn pack(&self, container: &Rectangle, items: &[Rectangle]) -> Packing {
+        match self.heuristic {
+            Heuristic::Shelf => self.pack_into::<shelf::ShelfBin>(container, items),
+            Heuristic::Guillotine => self.pack_into::<guillotine::GuillotineBin>(container, items),
+            Heuristic::MaxRects => self.pack_into::<maxrects::MaxRectsBin>(container, items),
+        }
+    }
+
+    fn pack_into<B: Bin>(&self, container: &Rectangle, items: &[Rectangle]) -> Packing {
+        let mut bins: Vec<B> = Vec::new();
+        let mut placements = Vec::new();
+        let mut unplaced = Vec::new();
+
+        for item in self.order(items) {
+            let Rectangle { width, height } = items[item];
+            let Some((w, h, turned)) = orientations(width, height, self.allow_rotation)
+                .into_iter()
+                .find(|&(w, h, _)| w <= container.width && h <= container.height)
+            else {
+                unplaced.push(item);
+                continue;
+            };
+
+            // An item with no area takes no room, so it goes in the corner of the first bin.
+            let found = if w == 0 || h == 0 {
+                (!bins.is_empty()).then(|| (0, rect(0, 0, w, h), turned))
+            } else {
+                bins.iter_mut().enumerate().find_map(|(bin, free)| {
+                    let (rect, rotated) = free.insert(width, height, self.allow_rotation)?;
+                    Some((bin, rect, rotated))
+                })
+            };
+            let (bin, rect, rotated) = match found {
+                Some(found) => found,
+                None if w == 0 || h == 0 => {
+                    bins.push(B::new(container.width, container.height));
+                    (0, rect(0, 0, w, h), turned)
+                }
+                None => {
+                    let mut free = B::new(container.width, container.height);
+                    let (rect, rotated) = free
+                        .insert(width, height, self.allow_rotation)
+                        .expect("an empty bin holds anything that fits the container");
+                    bins.push(free);
+                    (bins.len() - 1, rect, rotated)
+                }
+            };
+            placements.push(Placement {
+                item,
+                bin,
+                rect,
+                rotated,
+            });
+        }
+
+        unplaced.sort_unstable();
+        Packing {
+            container: container.clone(),
+            bins: bins.len(),
+            placements,
+            unplaced,
+        }
+    }
+
+    // Item indexes, biggest first. Shelves fill best in order of height, with each item lying
+    // on its longer side when it may be turned; the others go by area. Ties keep their order.
+    fn order(&self, items: &[Rectangle]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        let key = |item: &Rectangle| match (self.heuristic, self.allow_rotation) {
+            (Heuristic::Shelf, false) => (u64::from(item.height), u64::from(item.width)),
+            (Heuristic::Shelf, true) => (
+                u64::from(item.width.min(item.height)),
+                u64::from(item.width.max(item.height)),
+            ),
+            _ => (
+                u64::from(item.width) * u64::from(item.height),
+                u64::from(item.width.max(item.height)),
+            ),
+        };
+        order.sort_by_key(|&item| std::cmp::Reverse(key(&items[item])));
+        order
+    }
+}
+
+impl Packing {
+    pub fn in_bin(&self, bin: usize) -> impl Iterator<Item = &Placement> {
+        self.placements
+            .iter()
+            .filter(move |placement| placement.bin == bin)
+    }
+
+    // How much of a bin's area is covered, as a percentage.
+    pub fn utilization(&self, bin: usize) -> f64 {
+        let used: u64 = self
+            .in_bin(bin)
+            .map(|placement| placement.rect.area())
+            .sum();
+        percentage(used, self.container_area())
+    }
+
+    pub fn utilizations(&self) -> Vec<f64> {
+        (0..self.bins).map(|bin| self.utilization(bin)).collect()
+    }
+
+    // How much of all the bins together is covered, as a percentage.
+    pub fn total_utilization(&self) -> f64 {
+        let used: u64 = self
+            .placements
+            .iter()
+            .map(|placement| placement.rect.area())
+            .sum();
+        percentage(used, self.container_area() * self.bins as u64)
+    }
+
+    fn container_area(&self) -> u64 {
+        u64::from(self.container.width) * u64::from(self.container.height)
+    }
+}
+
+// A rectangle the packer has already checked lies inside the container.
+fn rect(x: u32, y: u32, width: u32, height: u32) -> Rect {
+    Rect::new(x, y, width, height).expect("inside the container")
+}
+
+fn percentage(used: u64, available: u64) -> f64 {
+    if available == 0 {
+        return 0.0;
+    }
+    used as f64 / available as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const HEURISTICS: [Heuristic; 3] =
+        [Heuristic::Shelf, Heuristic::Guillotine, Heuristic::MaxRects];
+
+    fn rectangle(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    // Everything `pack` promises: each item is placed once, at its own size, inside the
+    // container and clear of the other items in its bin, unless it is too big to place at all.
+    fn check(packer: Packer, container: &Rectangle, items: &[Rectangle]) -> Packing {
+        let packing = packer.pack(container, items);
+        let bounds = Rect::from(container.clone());
+
+        let mut seen = vec![false; items.len()];
+        for placement in &packing.placements {
+            let item = &items[placement.item];
+            let size = if placement.rotated {
+                rectangle(item.height, item.width)
+            } else {
+                item.clone()
+            };
+            assert!(!seen[placement.item], "{placement:?} placed twice");
+            seen[placement.item] = true;
+            assert_eq!(placement.rect.size(), size, "{placement:?}");
+            assert!(placement.bin < packing.bins, "{placement:?}");
+            assert!(
+                bounds.contains_rect(&placement.rect),
+                "{placement:?} out of bounds"
+            );
+            assert!(packer.allow_rotation || !placement.rotated);
+        }
+        for (i, a) in packing.placements.iter().enumerate() {
+            for b in &packing.placements[i + 1..] {
+                assert!(
+                    a.bin != b.bin || !a.rect.intersects(&b.rect),
+                    "{a:?} overlaps {b:?}"
+                );
+            }
+        }
+        for &item in &packing.unplaced {
+            assert!(!seen[item]);
+            seen[item] = true;
+            let Rectangle { width, height } = items[item];
+            assert!(
+                orientations(width, height, packer.allow_rotation)
+                    .iter()
+                    .all(|&(w, h, _)| w > container.width || h > container.height)
+            );
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        packing
+    }
+
+    #[test]
+    fn fills_a_container_exactly() {
+        let container = rectangle(10, 10);
+        let items = [rectangle(5, 5), rectangle(5, 5), rectangle(10, 5)];
+
+        for heuristic in HEURISTICS {
+            let packing = check(Packer::new(heuristic), &container, &items);
+            assert_eq!(packing.bins, 1, "{heuristic:?}");
+            assert_eq!(packing.utilizations(), [100.0], "{heuristic:?}");
+        }
+    }
+
+    #[test]
+    fn opens_more_bins_and_reports_what_does_not_fit() {
+        let container = rectangle(10, 4);
+        let items = [
+            rectangle(6, 4),
+            rectangle(2, 12),
+            rectangle(6, 4),
+            rectangle(11, 1),
+            rectangle(4, 2),
+        ];
+
+        for heuristic in HEURISTICS {
+            let packing = check(Packer::new(heuristic), &container, &items);
+            assert_eq!(packing.unplaced, [1, 3], "{heuristic:?}");
+            assert_eq!(packing.bins, 2, "{heuristic:?}");
+            assert_eq!(packing.utilizations(), [80.0, 60.0], "{heuristic:?}");
+            assert_eq!(packing.total_utilization(), 70.0, "{heuristic:?}");
+        }
+    }
+
+    #[test]
+    fn rotation_fits_items_that_only_fit_turned() {
+        let container = rectangle(12, 2);
+        let items = [rectangle(2, 6), rectangle(2, 6)];
+
+        for heuristic in HEURISTICS {
+            let packing = check(Packer::new(heuristic), &container, &items);
+            assert_eq!(packing.unplaced, [0, 1], "{heuristic:?}");
+
+            let packer = Packer {
+                heuristic,
+                allow_rotation: true,
+            };
+            let packing = check(packer, &container, &items);
+            assert_eq!(packing.bins, 1, "{heuristic:?}");
+            assert!(packing.placements.iter().all(|placement| placement.rotated));
+            assert_eq!(packing.total_utilization(), 100.0, "{heuristic:?}");
+        }
+    }
+
+    #[test]
+    fn shelves_waste_the_room_above_short_items() {
+        let container = rectangle(10, 10);
+        let items = [rectangle(6, 10), rectangle(4, 4), rectangle(4, 4)];
+
+        let shelf = check(Packer::new(Heuristic::Shelf), &container, &items);
+        assert_eq!(shelf.bins, 2);
+        for heuristic in [Heuristic::Guillotine, Heuristic::MaxRects] {
+            let packing = check(Packer::new(heuristic), &container, &items);
+            assert_eq!(packing.bins, 1, "{heuristic:?}");
+            assert_eq!(packing.utilizations(), [92.0], "{heuristic:?}");
+        }
+    }
+
+    #[test]
+    fn empty_input_and_empty_items() {
+        let container = rectangle(5, 5);
+
+        for heuristic in HEURISTICS {
+            let packing = check(Packer::new(heuristic), &container, &[]);
+            assert_eq!(packing.bins, 0);
+            assert_eq!(packing.total_utilization(), 0.0);
+
+            let items = [rectangle(0, 3), rectangle(5, 5), rectangle(2, 0)];
+            let packing = check(Packer::new(heuristic), &container, &items);
+            assert_eq!(packing.bins, 1, "{heuristic:?}");
+        }
+    }
+
+    fn heuristic() -> impl Strategy<Value = Heuristic> {
+        prop::sample::select(HEURISTICS.to_vec())
+    }
+
+    proptest! {
+        #[test]
+        fn placements_never_overlap_and_stay_in_bounds(
+            heuristic in heuristic(),
+            allow_rotation in any::<bool>(),
+            (width, height) in (1..40u32, 1..40u32),
+            items in prop::collection::vec((0..45u32, 0..45u32), 0..40),
+        ) {
+            let items: Vec<Rectangle> = items
+                .into_iter()
+                .map(|(width, height)| rectangle(width, height))
+                .collect();
+            let packer = Packer { heuristic, allow_rotation };
+            let packing = check(packer, &rectangle(width, height), &items);
+            for utilization in packing.utilizations() {
+                prop_assert!(utilization <= 100.0);
+            }
+        }
+    }
+}
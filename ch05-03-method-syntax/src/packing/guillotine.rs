@@ -0,0 +1,52 @@
+use super::{Bin, orientations, rect};
+use crate::geometry::Rect;
+
+// The free space as rectangles that never overlap, since each cut splits one in two.
+pub(super) struct GuillotineBin {
+    free: Vec<Rect>,
+}
+
+impl Bin for GuillotineBin {
+    fn new(width: u32, height: u32) -> Self {
+        GuillotineBin {
+            free: vec![rect(0, 0, width, height)],
+        }
+    }
+
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rect, bool)> {
+        let orientations = orientations(width, height, allow_rotation);
+
+        // The smallest free rectangle the item fits in.
+        let (_, index, w, h, rotated) = self
+            .free
+            .iter()
+            .enumerate()
+            .flat_map(|(index, free)| {
+                orientations
+                    .iter()
+                    .filter(|&&(w, h, _)| w <= free.width() && h <= free.height())
+                    .map(move |&(w, h, rotated)| (free.area(), index, w, h, rotated))
+            })
+            .min_by_key(|&(area, ..)| area)?;
+
+        let free = self.free.swap_remove(index);
+        let placed = rect(free.x(), free.y(), w, h);
+        let (left_over_width, left_over_height) = (free.width() - w, free.height() - h);
+
+        // Cutting along the shorter leftover side keeps the larger piece as large as it can be.
+        let (right, below) = if left_over_width <= left_over_height {
+            (
+                rect(placed.right(), free.y(), left_over_width, h),
+                rect(free.x(), placed.bottom(), free.width(), left_over_height),
+            )
+        } else {
+            (
+                rect(placed.right(), free.y(), left_over_width, free.height()),
+                rect(free.x(), placed.bottom(), w, left_over_height),
+            )
+        };
+        self.free
+            .extend([right, below].into_iter().filter(|piece| !piece.is_empty()));
+        Some((placed, rotated))
+    }
+}
@@ -0,0 +1,94 @@
+use super::{Bin, orientations, rect};
+use crate::geometry::Rect;
+
+// The free space as every largest rectangle that fits in it. These overlap, which lets an
+// item use space a guillotine cut would have split up.
+pub(super) struct MaxRectsBin {
+    free: Vec<Rect>,
+}
+
+impl Bin for MaxRectsBin {
+    fn new(width: u32, height: u32) -> Self {
+        MaxRectsBin {
+            free: vec![rect(0, 0, width, height)],
+        }
+    }
+
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rect, bool)> {
+        let orientations = orientations(width, height, allow_rotation);
+
+        // Best short side fit: the free rectangle left with the least room beside the item,
+        // then the least room along its other side.
+        let (_, placed, rotated) = self
+            .free
+            .iter()
+            .flat_map(|free| {
+                orientations
+                    .iter()
+                    .filter(|&&(w, h, _)| w <= free.width() && h <= free.height())
+                    .map(move |&(w, h, rotated)| {
+                        let (dw, dh) = (free.width() - w, free.height() - h);
+                        let fit = (dw.min(dh), dw.max(dh));
+                        (fit, rect(free.x(), free.y(), w, h), rotated)
+                    })
+            })
+            .min_by_key(|&(fit, ..)| fit)?;
+
+        let mut free = Vec::with_capacity(self.free.len() + 4);
+        for space in self.free.drain(..) {
+            if space.intersects(&placed) {
+                free.extend(split(&space, &placed));
+            } else {
+                free.push(space);
+            }
+        }
+        self.free = prune(free);
+        Some((placed, rotated))
+    }
+}
+
+// What is left of `space` around `placed`: the largest rectangles to its left, right, above
+// and below, which overlap each other at the corners.
+fn split(space: &Rect, placed: &Rect) -> impl Iterator<Item = Rect> {
+    let left = rect(
+        space.x(),
+        space.y(),
+        placed.x().saturating_sub(space.x()),
+        space.height(),
+    );
+    let right = rect(
+        placed.right(),
+        space.y(),
+        space.right().saturating_sub(placed.right()),
+        space.height(),
+    );
+    let above = rect(
+        space.x(),
+        space.y(),
+        space.width(),
+        placed.y().saturating_sub(space.y()),
+    );
+    let below = rect(
+        space.x(),
+        placed.bottom(),
+        space.width(),
+        space.bottom().saturating_sub(placed.bottom()),
+    );
+    [left, right, above, below]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+}
+
+// Drops every free rectangle that lies inside another, keeping one of any duplicates.
+fn prune(free: Vec<Rect>) -> Vec<Rect> {
+    free.iter()
+        .enumerate()
+        .filter(|&(i, space)| {
+            !free
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && other.contains_rect(space) && (other != space || j < i))
+        })
+        .map(|(_, space)| *space)
+        .collect()
+}
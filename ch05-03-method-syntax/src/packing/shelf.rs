@@ -0,0 +1,62 @@
+use super::{Bin, orientations, rect};
+use crate::geometry::Rect;
+
+// A row of items along the top of the space left in the bin.
+struct Shelf {
+    y: u32,
+    height: u32,
+    // How far along the row is already taken.
+    used: u32,
+}
+
+pub(super) struct ShelfBin {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+}
+
+impl Bin for ShelfBin {
+    fn new(width: u32, height: u32) -> Self {
+        ShelfBin {
+            width,
+            height,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rect, bool)> {
+        let orientations = orientations(width, height, allow_rotation);
+
+        // The shelf the item fills the most of, wasting the least height above it.
+        let best = self
+            .shelves
+            .iter()
+            .enumerate()
+            .flat_map(|(shelf, row)| {
+                orientations
+                    .iter()
+                    .filter(|&&(w, h, _)| h <= row.height && w <= self.width - row.used)
+                    .map(move |&(w, h, rotated)| (row.height - h, shelf, w, h, rotated))
+            })
+            .min_by_key(|&(waste, ..)| waste);
+        if let Some((_, shelf, w, h, rotated)) = best {
+            let row = &mut self.shelves[shelf];
+            let placed = rect(row.used, row.y, w, h);
+            row.used += w;
+            return Some((placed, rotated));
+        }
+
+        // Otherwise a new shelf under the last one, as low as the item can lie.
+        let top = self.shelves.last().map_or(0, |row| row.y + row.height);
+        let (w, h, rotated) = orientations
+            .into_iter()
+            .filter(|&(w, h, _)| w <= self.width && h <= self.height - top)
+            .min_by_key(|&(_, h, _)| h)?;
+        self.shelves.push(Shelf {
+            y: top,
+            height: h,
+            used: w,
+        });
+        Some((rect(0, top, w, h), rotated))
+    }
+}
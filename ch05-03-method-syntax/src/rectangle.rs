@@ -0,0 +1,63 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+// Everything within this impl block will be associated with the Rectangle type.
+impl Rectangle {
+    // In the signature for area, we use &self instead of rectangle: &Rectangle.
+    // The &self is actually short for self: &Self
+
+    // Within an impl block, the type Self is an alias for the type that the impl block is for.
+    // Methods must have a parameter named self of type Self for their first parameter,
+    // so Rust lets you abbreviate this with only the name self in the first parameter spot.
+    // Note that we still need to use the & in front of the self shorthand to indicate that this method borrows the Self instance,
+    // just as we did in rectangle: &Rectangle.
+    // Methods can take ownership of self, borrow self immutably, as we’ve done here, or borrow self mutably,
+    // just as they can any other parameter.
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    // Use `&mut self` when the method needs to modify the instance.
+    pub fn double_the_width(&mut self) {
+        self.width *= 2;
+    }
+
+    //  Having a method that takes ownership of the instance by using just self as the first parameter is rare;
+    // this technique is usually used when the method transforms self into something else and
+    // you want to prevent the caller from using the original instance after the transformation.
+    pub fn double_the_width_with_move(mut self) {
+        self.width *= 2;
+    }
+
+    // Note that we can choose to give a method the same name as one of the struct’s fields.
+    pub fn width(&self) -> bool {
+        self.width > 0
+    }
+
+    // Methods can take multiple parameters that we add to the signature after the self parameter, and
+    // those parameters work just like parameters in functions.
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    // We can define associated functions that don’t have self as their first parameter (and thus are not methods) because
+    // they don’t need an instance of the type to work with.
+    // To call this associated function, we use the :: syntax with the struct name;
+    // This function is namespaced by the struct.
+    pub fn square(length: u32) -> Self {
+        Self {
+            width: length,
+            height: length,
+        }
+    }
+}
+
+// Each struct is allowed to have multiple impl blocks
+impl Rectangle {
+    pub fn print(&self) {
+        println!("Width: {}, Height: {}", self.width, self.height);
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+
+use crate::geometry::{Rect, Shape};
+
+mod ascii;
+mod svg;
+
+pub use ascii::Ascii;
+pub use svg::Svg;
+
+// Shapes to draw together, each with an optional label and color, ready to render as ASCII
+// art for a terminal or as an SVG document. Shapes added later are drawn on top.
+//
+// Both renderers draw only the part of the grid the shapes cover, from the top-left of the
+// leftmost, topmost shape to the bottom-right of the furthest one.
+#[derive(Debug, Default)]
+pub struct Drawing<'a> {
+    items: Vec<Item<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Item<'a> {
+    shape: &'a dyn Shape,
+    label: Option<String>,
+    color: Option<Color>,
+}
+
+// The terminal's eight usual colors, less black and white, which vanish against one
+// background or the other, and any other color as red, green and blue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderError {
+    // The scale is negative, infinite or NaN. An SVG also needs it above 0, or the document
+    // has no size.
+    InvalidScale(f64),
+    // An ASCII drawing would take more than a million characters at this scale.
+    TooLarge { columns: f64, rows: f64 },
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::InvalidScale(scale) => write!(f, "cannot draw at a scale of {scale}"),
+            RenderError::TooLarge { columns, rows } => write!(
+                f,
+                "the drawing would be {columns} columns by {rows} rows, too large to draw"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl<'a> Drawing<'a> {
+    pub fn new() -> Drawing<'a> {
+        Drawing { items: Vec::new() }
+    }
+
+    // Adds a shape, returning it so that a label and color can be set:
+    //
+    //     drawing.add(&rect).label("rect").color(Color::Red);
+    pub fn add(&mut self, shape: &'a dyn Shape) -> &mut Item<'a> {
+        self.items.push(Item {
+            shape,
+            label: None,
+            color: None,
+        });
+        self.items.last_mut().expect("just pushed")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // The part of the grid the drawing covers, or None if it has no shapes.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::bounding_box(self.items.iter().map(|item| item.shape.bounding_box()))
+    }
+}
+
+impl Item<'_> {
+    pub fn label(&mut self, label: impl Into<String>) -> &mut Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use super::*;
+    use crate::{
+        geometry::{Circle, Point, Polygon, Triangle},
+        rectangle::Rectangle,
+    };
+
+    // Compares `actual` with tests/golden/`name`. Run the tests with UPDATE_GOLDEN=1 to write
+    // the files instead, after checking the new output by eye.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+            panic!(
+                "{}: {err}; run with UPDATE_GOLDEN=1 to create it",
+                path.display()
+            )
+        });
+        assert!(
+            actual == expected,
+            "{name} changed; run with UPDATE_GOLDEN=1 if that is intended\n\
+             --- expected\n{expected}\n--- actual\n{actual}"
+        );
+    }
+
+    struct Scene {
+        rectangle: Rectangle,
+        rect: Rect,
+        circle: Circle,
+        triangle: Triangle,
+        hexagon: Polygon,
+    }
+
+    fn scene() -> Scene {
+        let p = Point::new;
+        Scene {
+            rectangle: Rectangle {
+                width: 12,
+                height: 6,
+            },
+            rect: Rect::new(8, 3, 10, 6).unwrap(),
+            circle: Circle::new(p(24, 6), 5).unwrap(),
+            triangle: Triangle::new(p(0, 8), p(10, 14), p(0, 14)).unwrap(),
+            hexagon: Polygon::new(vec![p(16, 10), p(22, 10), p(25, 12), p(22, 14), p(16, 14)])
+                .unwrap(),
+        }
+    }
+
+    fn drawing(scene: &Scene) -> Drawing<'_> {
+        let mut drawing = Drawing::new();
+        drawing.add(&scene.rectangle).label("rectangle");
+        drawing.add(&scene.rect).label("rect").color(Color::Red);
+        drawing
+            .add(&scene.circle)
+            .label("circle")
+            .color(Color::Rgb(0x33, 0x66, 0x99));
+        drawing.add(&scene.triangle).color(Color::Green);
+        drawing.add(&scene.hexagon).label("<hexagon & co>");
+        drawing
+    }
+
+    #[test]
+    fn ascii() {
+        let scene = scene();
+        let drawing = drawing(&scene);
+
+        assert_golden("scene.txt", &Ascii::new().render(&drawing).unwrap());
+        let half = Ascii {
+            scale: 0.5,
+            ..Ascii::new()
+        };
+        assert_golden("scene-half.txt", &half.render(&drawing).unwrap());
+        let colored = Ascii {
+            colors: true,
+            ..Ascii::new()
+        };
+        assert_golden("scene-colors.txt", &colored.render(&drawing).unwrap());
+    }
+
+    #[test]
+    fn ascii_scale_is_checked() {
+        let scene = scene();
+        let drawing = drawing(&scene);
+        let scaled = |scale| {
+            Ascii {
+                scale,
+                ..Ascii::new()
+            }
+            .render(&drawing)
+        };
+
+        assert_eq!(scaled(-1.0), Err(RenderError::InvalidScale(-1.0)));
+        assert_eq!(
+            scaled(f64::INFINITY),
+            Err(RenderError::InvalidScale(f64::INFINITY))
+        );
+        assert!(matches!(
+            scaled(f64::NAN),
+            Err(RenderError::InvalidScale(_))
+        ));
+        assert_eq!(
+            scaled(1e9),
+            Err(RenderError::TooLarge {
+                columns: 58e9,
+                rows: 14e9
+            })
+        );
+        assert!(scaled(0.0).unwrap().starts_with("\n"));
+
+        let line = Rect::new(0, 0, 0, u32::MAX).unwrap();
+        let mut tall = Drawing::new();
+        tall.add(&line);
+        assert_eq!(
+            Ascii::new().render(&tall),
+            Err(RenderError::TooLarge {
+                columns: 0.0,
+                rows: f64::from(u32::MAX)
+            })
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let scene = scene();
+        let drawing = drawing(&scene);
+
+        assert_golden("scene.svg", &Svg::new().render(&drawing).unwrap());
+        assert_golden(
+            "scene-scaled.svg",
+            &Svg { scale: 3.0 }.render(&drawing).unwrap(),
+        );
+        for scale in [0.0, -1.0, f64::INFINITY] {
+            assert_eq!(
+                Svg { scale }.render(&drawing),
+                Err(RenderError::InvalidScale(scale))
+            );
+        }
+        assert!(Svg { scale: f64::NAN }.render(&drawing).is_err());
+    }
+
+    #[test]
+    fn crops_to_the_shapes() {
+        let far = Rect::new(1000, 2000, 3, 2).unwrap();
+        let mut drawing = Drawing::new();
+        drawing.add(&far).label("far");
+
+        assert_eq!(drawing.bounds(), Some(far));
+        assert_eq!(
+            Ascii::new().render(&drawing),
+            Ok("######\n#far##\n\n# far\n".to_string())
+        );
+        assert!(
+            Svg::new()
+                .render(&drawing)
+                .unwrap()
+                .contains(r#"width="30" height="20" viewBox="1000 2000 3 2""#)
+        );
+    }
+
+    #[test]
+    fn empty_drawings() {
+        let drawing = Drawing::new();
+        assert_eq!(drawing.bounds(), None);
+        assert_eq!(Ascii::new().render(&drawing), Ok(String::new()));
+        assert_eq!(
+            Svg::new().render(&drawing).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\" \
+             viewBox=\"0 0 0 0\">\n</svg>\n"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use super::{Color, Drawing, RenderError};
+
+// The character each shape is filled with, by the order it was added in, so that shapes
+// that touch can still be told apart.
+const FILLS: [char; 8] = ['#', '*', '+', 'o', '%', '@', '=', 'x'];
+
+// The most characters a drawing may take, a thousand lines of a thousand, well past what
+// any terminal shows.
+const MAX_CELLS: f64 = 1_000_000.0;
+
+// Draws shapes as characters, one per cell of a grid laid over them. A cell shows the
+// topmost shape its center is in, and each label is written across the middle of its shape,
+// cut short if the shape is too narrow, and again in a key underneath.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ascii {
+    // Rows of characters per grid unit. A unit is twice as many columns wide, since terminal
+    // characters are about twice as tall as they are wide. A scale of 0 draws nothing, and
+    // `render` refuses one that is negative or not finite.
+    pub scale: f64,
+    // Colors the shapes and labels with ANSI escape codes, which most terminals show.
+    pub colors: bool,
+}
+
+impl Ascii {
+    pub fn new() -> Ascii {
+        Ascii {
+            scale: 1.0,
+            colors: false,
+        }
+    }
+
+    pub fn render(&self, drawing: &Drawing) -> Result<String, RenderError> {
+        if !self.scale.is_finite() || self.scale < 0.0 {
+            return Err(RenderError::InvalidScale(self.scale));
+        }
+        let Some(bounds) = drawing.bounds() else {
+            return Ok(String::new());
+        };
+        let (per_column, per_row) = (2.0 * self.scale, self.scale);
+        let column = |x: u32| f64::from(x - bounds.x()) * per_column;
+        let row = |y: u32| f64::from(y - bounds.y()) * per_row;
+        let (columns, rows) = (column(bounds.right()).ceil(), row(bounds.bottom()).ceil());
+        // Every row is allocated even when it holds no columns, so each side counts as at least 1.
+        if columns.max(1.0) * rows.max(1.0) > MAX_CELLS {
+            return Err(RenderError::TooLarge { columns, rows });
+        }
+        let (columns, rows) = (columns as usize, rows as usize);
+
+        // Each cell holds its character and the shape it belongs to, for the color.
+        let mut grid: Vec<Vec<Option<(char, usize)>>> = vec![vec![None; columns]; rows];
+        for (index, item) in drawing.items.iter().enumerate() {
+            let outline = item.shape.outline();
+            let fill = FILLS[index % FILLS.len()];
+            let bounds_of_item = item.shape.bounding_box();
+            let (left, right) = (
+                column(bounds_of_item.x()).floor() as usize,
+                cells(column(bounds_of_item.right())).min(columns),
+            );
+            let (top, bottom) = (
+                row(bounds_of_item.y()).floor() as usize,
+                cells(row(bounds_of_item.bottom())).min(rows),
+            );
+            for (r, line) in grid.iter_mut().enumerate().take(bottom).skip(top) {
+                let y = f64::from(bounds.y()) + (r as f64 + 0.5) / per_row;
+                for (c, cell) in line.iter_mut().enumerate().take(right).skip(left) {
+                    let x = f64::from(bounds.x()) + (c as f64 + 0.5) / per_column;
+                    if outline.contains(x, y) {
+                        *cell = Some((fill, index));
+                    }
+                }
+            }
+        }
+
+        for (index, item) in drawing.items.iter().enumerate() {
+            let Some(label) = &item.label else {
+                continue;
+            };
+            let bounds_of_item = item.shape.bounding_box();
+            let left = column(bounds_of_item.x()).floor() as usize;
+            let right = cells(column(bounds_of_item.right())).min(columns);
+            let middle = f64::from(bounds_of_item.y()) + f64::from(bounds_of_item.height()) / 2.0;
+            let r = ((middle - f64::from(bounds.y())) * per_row) as usize;
+            let Some(line) = grid.get_mut(r) else {
+                continue;
+            };
+            let width = right.saturating_sub(left);
+            let text: String = label.chars().take(width).collect();
+            let text: Vec<char> = text.trim_end().chars().collect();
+            let start = left + (width - text.len()) / 2;
+            for (cell, ch) in line[start..].iter_mut().zip(text) {
+                *cell = Some((ch, index));
+            }
+        }
+
+        let mut out = String::new();
+        for line in &grid {
+            let end = line
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |last| last + 1);
+            let mut painted = None;
+            for cell in &line[..end] {
+                let color = cell.and_then(|(_, index)| drawing.items[index].color);
+                if self.colors && color != painted {
+                    switch_color(&mut out, painted, color);
+                    painted = color;
+                }
+                out.push(cell.map_or(' ', |(ch, _)| ch));
+            }
+            if self.colors {
+                switch_color(&mut out, painted, None);
+            }
+            out.push('\n');
+        }
+
+        let mut key = drawing
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| Some((index, item.label.as_ref()?)))
+            .peekable();
+        if key.peek().is_some() {
+            out.push('\n');
+        }
+        for (index, label) in key {
+            let color = drawing.items[index].color.filter(|_| self.colors);
+            switch_color(&mut out, None, color);
+            out.push(FILLS[index % FILLS.len()]);
+            switch_color(&mut out, color, None);
+            out.push(' ');
+            out.push_str(label);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+impl Default for Ascii {
+    fn default() -> Self {
+        Ascii::new()
+    }
+}
+
+// How many whole cells it takes to cover `length` cells' worth of the grid.
+fn cells(length: f64) -> usize {
+    length.ceil() as usize
+}
+
+fn switch_color(out: &mut String, from: Option<Color>, to: Option<Color>) {
+    if from.is_some() {
+        out.push_str("\x1b[0m");
+    }
+    if let Some(color) = to {
+        out.push_str(&ansi(color));
+    }
+}
+
+fn ansi(color: Color) -> String {
+    let code = match color {
+        Color::Red => "31".to_string(),
+        Color::Green => "32".to_string(),
+        Color::Yellow => "33".to_string(),
+        Color::Blue => "34".to_string(),
+        Color::Magenta => "35".to_string(),
+        Color::Cyan => "36".to_string(),
+        Color::Gray => "90".to_string(),
+        Color::Rgb(r, g, b) => format!("38;2;{r};{g};{b}"),
+    };
+    format!("\x1b[{code}m")
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use super::{Color, Drawing, RenderError};
+use crate::geometry::Outline;
+
+// Draws shapes as an SVG document, with the grid's own coordinates, so that a shape at
+// (10, 20) is at (10, 20) in the document too. Colored shapes are filled see-through in
+// their color; the rest are drawn as black outlines. Labels go in the middle of their shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Svg {
+    // Pixels per grid unit when the document is shown at its natural size. `render` refuses
+    // a scale that is not above 0 or not finite.
+    pub scale: f64,
+}
+
+// How tall labels are, and how thick edges are, in pixels whatever the scale.
+const FONT_SIZE: f64 = 12.0;
+const STROKE_WIDTH: f64 = 1.0;
+
+impl Svg {
+    pub fn new() -> Svg {
+        Svg { scale: 10.0 }
+    }
+
+    pub fn render(&self, drawing: &Drawing) -> Result<String, RenderError> {
+        if !self.scale.is_finite() || self.scale <= 0.0 {
+            return Err(RenderError::InvalidScale(self.scale));
+        }
+        let bounds = drawing.bounds().unwrap_or_default();
+        let mut out = String::new();
+        // Writing to a String cannot fail.
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            number(f64::from(bounds.width()) * self.scale),
+            number(f64::from(bounds.height()) * self.scale),
+            bounds.x(),
+            bounds.y(),
+            bounds.width(),
+            bounds.height(),
+        );
+
+        for item in &drawing.items {
+            let style = match item.color {
+                Some(color) => format!(
+                    r#"fill="{color}" fill-opacity="0.4" stroke="{color}""#,
+                    color = css(color)
+                ),
+                None => r#"fill="none" stroke="black""#.to_string(),
+            };
+            let stroke = number(STROKE_WIDTH / self.scale);
+            let _ = match item.shape.outline() {
+                Outline::Rect(rect) => writeln!(
+                    out,
+                    r#"  <rect x="{}" y="{}" width="{}" height="{}" {style} stroke-width="{stroke}"/>"#,
+                    rect.x(),
+                    rect.y(),
+                    rect.width(),
+                    rect.height(),
+                ),
+                Outline::Circle { center, radius } => writeln!(
+                    out,
+                    r#"  <circle cx="{}" cy="{}" r="{radius}" {style} stroke-width="{stroke}"/>"#,
+                    center.x, center.y,
+                ),
+                Outline::Polygon(vertices) => {
+                    let points: Vec<String> = vertices
+                        .iter()
+                        .map(|point| format!("{},{}", point.x, point.y))
+                        .collect();
+                    writeln!(
+                        out,
+                        r#"  <polygon points="{}" {style} stroke-width="{stroke}"/>"#,
+                        points.join(" "),
+                    )
+                }
+            };
+        }
+
+        // After every shape, so that no shape covers a label.
+        for item in &drawing.items {
+            let Some(label) = &item.label else {
+                continue;
+            };
+            let bounds_of_item = item.shape.bounding_box();
+            let fill = item
+                .color
+                .map(|color| format!(r#" fill="{}""#, css(color)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                r#"  <text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central"{fill}>{}</text>"#,
+                number(f64::from(bounds_of_item.x()) + f64::from(bounds_of_item.width()) / 2.0),
+                number(f64::from(bounds_of_item.y()) + f64::from(bounds_of_item.height()) / 2.0),
+                number(FONT_SIZE / self.scale),
+                escape(label),
+            );
+        }
+
+        out.push_str("</svg>\n");
+        Ok(out)
+    }
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Svg::new()
+    }
+}
+
+fn css(color: Color) -> String {
+    match color {
+        Color::Red => "red".to_string(),
+        Color::Green => "green".to_string(),
+        Color::Yellow => "gold".to_string(),
+        Color::Blue => "blue".to_string(),
+        Color::Magenta => "magenta".to_string(),
+        Color::Cyan => "cyan".to_string(),
+        Color::Gray => "gray".to_string(),
+        Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+    }
+}
+
+// At most three decimal places and no trailing zeros: "1.5", not "1.500".
+fn number(value: f64) -> String {
+    if !value.is_finite() {
+        return "0".to_string();
+    }
+    let text = format!("{value:.3}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    text.to_string()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,487 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::geometry::{Point, Rect};
+
+// How many rectangles a node holds before it is split into quarters.
+const NODE_CAPACITY: usize = 8;
+
+// Names one rectangle in a `SpatialIndex`. Ids are never reused, even after a removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(u64);
+
+// Finds the rectangles near a point or overlapping a window without looking at all of them,
+// which makes checking every rectangle against every other O(n log n) rather than O(n²) for
+// rectangles that are spread out.
+//
+// It is a quadtree over the whole grid. Each node holds the rectangles that fit inside it
+// but inside none of its quarters, so a rectangle across the middle of the grid stays at the
+// top, and a node with too many rectangles splits until it is a single unit wide or high.
+// Every query result is sorted by id, except `nearest`, which goes by distance.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<T> {
+    root: Node,
+    entries: HashMap<Id, (Rect, T)>,
+    next_id: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Rect,
+    items: Vec<(Id, Rect)>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+impl<T> SpatialIndex<T> {
+    pub fn new() -> SpatialIndex<T> {
+        SpatialIndex {
+            root: Node::new(Rect::new(0, 0, u32::MAX, u32::MAX).expect("the grid fits itself")),
+            entries: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn insert(&mut self, rect: Rect, value: T) -> Id {
+        let id = Id(self.next_id);
+        self.next_id += 1;
+        self.root.insert(id, rect);
+        self.entries.insert(id, (rect, value));
+        id
+    }
+
+    // Takes a rectangle out, returning it and its value, or None if it is not in the index.
+    pub fn remove(&mut self, id: Id) -> Option<(Rect, T)> {
+        let (rect, value) = self.entries.remove(&id)?;
+        let removed = self.root.remove(id, &rect);
+        debug_assert!(removed, "{id:?} was in the entries but not the tree");
+        Some((rect, value))
+    }
+
+    pub fn get(&self, id: Id) -> Option<(&Rect, &T)> {
+        self.entries.get(&id).map(|(rect, value)| (rect, value))
+    }
+
+    // The rectangles that share some area with `window`, by the rules of `Rect::intersects`.
+    pub fn query(&self, window: &Rect) -> Vec<(Id, &Rect, &T)> {
+        self.search(|rect| rect.intersects(window))
+    }
+
+    // The rectangles that `point` is inside, by the rules of `Rect::contains_point`.
+    pub fn at_point(&self, point: Point) -> Vec<(Id, &Rect, &T)> {
+        self.search(|rect| rect.contains_point(point))
+    }
+
+    // Up to `k` rectangles, nearest to `point` first, measured to the nearest edge; a point
+    // inside or on the edge of a rectangle is 0 away from it. Ties go to the lower id.
+    pub fn nearest(&self, point: Point, k: usize) -> Vec<(Id, &Rect, &T)> {
+        let mut found = Vec::new();
+        if k == 0 {
+            return found;
+        }
+
+        // Best first: a node is never nearer than its bounds, so by the time an item comes
+        // out of the queue, nothing still waiting can be nearer.
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse(Candidate::node(&self.root, point)));
+        while let Some(Reverse(candidate)) = queue.pop() {
+            match candidate.kind {
+                Kind::Item(id) => {
+                    found.push(self.entry(id));
+                    if found.len() == k {
+                        break;
+                    }
+                }
+                Kind::Node(node) => {
+                    queue.extend(node.items.iter().map(|&(id, rect)| {
+                        Reverse(Candidate {
+                            distance: distance_squared(point, &rect),
+                            kind: Kind::Item(id),
+                        })
+                    }));
+                    queue.extend(
+                        node.children
+                            .iter()
+                            .flat_map(|children| children.iter())
+                            .map(|child| Reverse(Candidate::node(child, point))),
+                    );
+                }
+            }
+        }
+        found
+    }
+
+    // Every pair of rectangles that overlap, each once with the lower id first, sorted.
+    pub fn overlapping_pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs: Vec<(Id, Id)> = self
+            .entries
+            .iter()
+            .flat_map(|(&id, (rect, _))| {
+                let mut overlapping = Vec::new();
+                self.root
+                    .search(&|other| other.intersects(rect), &mut overlapping);
+                overlapping
+                    .into_iter()
+                    .filter(move |&other| id < other)
+                    .map(move |other| (id, other))
+            })
+            .collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    fn search(&self, matches: impl Fn(&Rect) -> bool) -> Vec<(Id, &Rect, &T)> {
+        let mut ids = Vec::new();
+        self.root.search(&matches, &mut ids);
+        ids.sort_unstable();
+        ids.into_iter().map(|id| self.entry(id)).collect()
+    }
+
+    fn entry(&self, id: Id) -> (Id, &Rect, &T) {
+        let (rect, value) = &self.entries[&id];
+        (id, rect, value)
+    }
+}
+
+impl<T> Default for SpatialIndex<T> {
+    fn default() -> Self {
+        SpatialIndex::new()
+    }
+}
+
+impl<T> FromIterator<(Rect, T)> for SpatialIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (Rect, T)>>(iter: I) -> Self {
+        let mut index = SpatialIndex::new();
+        for (rect, value) in iter {
+            index.insert(rect, value);
+        }
+        index
+    }
+}
+
+impl Node {
+    fn new(bounds: Rect) -> Node {
+        Node {
+            bounds,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    // The first quarter a rectangle fits in. A rectangle with no width or height can lie on
+    // the line between two quarters; it always goes to the same one.
+    fn child_for(&mut self, rect: &Rect) -> Option<&mut Node> {
+        self.children
+            .as_deref_mut()?
+            .iter_mut()
+            .find(|child| child.bounds.contains_rect(rect))
+    }
+
+    fn insert(&mut self, id: Id, rect: Rect) {
+        if let Some(child) = self.child_for(&rect) {
+            return child.insert(id, rect);
+        }
+        self.items.push((id, rect));
+        if self.children.is_none() && self.items.len() > NODE_CAPACITY {
+            self.split();
+        }
+    }
+
+    fn split(&mut self) {
+        let Some(quarters) = quarters(&self.bounds) else {
+            return;
+        };
+        self.children = Some(Box::new(quarters.map(Node::new)));
+        for (id, rect) in std::mem::take(&mut self.items) {
+            self.insert(id, rect);
+        }
+    }
+
+    // Finds the rectangle where `insert` put it: in the deepest node that holds it.
+    fn remove(&mut self, id: Id, rect: &Rect) -> bool {
+        if let Some(child) = self.child_for(rect) {
+            if !child.remove(id, rect) {
+                return false;
+            }
+            // Quarters left empty are dropped, so a tree that shrinks does not stay deep.
+            if self
+                .children
+                .iter()
+                .flat_map(|children| children.iter())
+                .all(|child| child.items.is_empty() && child.children.is_none())
+            {
+                self.children = None;
+            }
+            return true;
+        }
+        match self.items.iter().position(|&(item, _)| item == id) {
+            Some(index) => {
+                self.items.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Collects every rectangle `matches` accepts. It is also asked about each node's bounds,
+    // and must accept any node that holds a rectangle it accepts.
+    fn search(&self, matches: &impl Fn(&Rect) -> bool, found: &mut Vec<Id>) {
+        if !matches(&self.bounds) {
+            return;
+        }
+        found.extend(
+            self.items
+                .iter()
+                .filter(|(_, rect)| matches(rect))
+                .map(|&(id, _)| id),
+        );
+        for child in self.children.iter().flat_map(|children| children.iter()) {
+            child.search(matches, found);
+        }
+    }
+}
+
+// Splits `bounds` through its middle, or None once it is too thin to split.
+fn quarters(bounds: &Rect) -> Option<[Rect; 4]> {
+    if bounds.width() < 2 || bounds.height() < 2 {
+        return None;
+    }
+    let (left, top) = (bounds.width() / 2, bounds.height() / 2);
+    let (right, bottom) = (bounds.width() - left, bounds.height() - top);
+    let (middle_x, middle_y) = (bounds.x() + left, bounds.y() + top);
+    let quarter = |x, y, width, height| Rect::new(x, y, width, height).expect("inside bounds");
+    Some([
+        quarter(bounds.x(), bounds.y(), left, top),
+        quarter(middle_x, bounds.y(), right, top),
+        quarter(bounds.x(), middle_y, left, bottom),
+        quarter(middle_x, middle_y, right, bottom),
+    ])
+}
+
+// Squared, so it stays a whole number; a u128 holds it across the whole grid.
+fn distance_squared(point: Point, rect: &Rect) -> u128 {
+    let gap = |p: u32, low: u32, high: u32| {
+        u128::from(low.saturating_sub(p)) + u128::from(p.saturating_sub(high))
+    };
+    let dx = gap(point.x, rect.x(), rect.right());
+    let dy = gap(point.y, rect.y(), rect.bottom());
+    dx * dx + dy * dy
+}
+
+// Something waiting in `nearest`'s queue, ordered by distance. At the same distance nodes
+// are opened before any item is returned, so that every item that far away is in the queue
+// and the lowest id among them comes out first.
+struct Candidate<'a> {
+    distance: u128,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Item(Id),
+    Node(&'a Node),
+}
+
+impl<'a> Candidate<'a> {
+    fn node(node: &'a Node, point: Point) -> Candidate<'a> {
+        Candidate {
+            distance: distance_squared(point, &node.bounds),
+            kind: Kind::Node(node),
+        }
+    }
+
+    fn rank(&self) -> (u128, bool, Option<Id>) {
+        match self.kind {
+            Kind::Node(_) => (self.distance, false, None),
+            Kind::Item(id) => (self.distance, true, Some(id)),
+        }
+    }
+}
+
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rect {
+        Rect::new(x, y, width, height).unwrap()
+    }
+
+    fn ids<T>(found: Vec<(Id, &Rect, &T)>) -> Vec<Id> {
+        found.into_iter().map(|(id, ..)| id).collect()
+    }
+
+    // What the index should find, by checking every rectangle.
+    fn scan(rects: &[(Id, Rect)], matches: impl Fn(&Rect) -> bool) -> Vec<Id> {
+        rects
+            .iter()
+            .filter(|(_, rect)| matches(rect))
+            .map(|&(id, _)| id)
+            .collect()
+    }
+
+    #[test]
+    fn window_and_point_queries() {
+        let mut index = SpatialIndex::new();
+        let a = index.insert(rect(0, 0, 10, 10), "a");
+        let b = index.insert(rect(10, 0, 5, 5), "b");
+        let c = index.insert(rect(u32::MAX - 5, u32::MAX - 5, 5, 5), "c");
+        let d = index.insert(rect(5, 5, 0, 3), "d");
+
+        assert_eq!(index.len(), 4);
+        assert_eq!(ids(index.query(&rect(8, 2, 4, 4))), [a, b]);
+        assert_eq!(ids(index.query(&rect(10, 10, 5, 5))), []);
+        assert_eq!(ids(index.query(&rect(0, 0, u32::MAX, u32::MAX))), [a, b, c]);
+        assert_eq!(ids(index.at_point(Point::new(9, 9))), [a]);
+        assert_eq!(ids(index.at_point(Point::new(10, 4))), [b]);
+        assert_eq!(
+            ids(index.at_point(Point::new(u32::MAX - 1, u32::MAX - 1))),
+            [c]
+        );
+        assert_eq!(index.get(d), Some((&rect(5, 5, 0, 3), &"d")));
+    }
+
+    #[test]
+    fn remove_takes_entries_out() {
+        let mut index: SpatialIndex<usize> = (0..100)
+            .map(|i| (rect(i * 3, i * 2, 4, 4), i as usize))
+            .collect();
+        let all = ids(index.query(&rect(0, 0, 1000, 1000)));
+        assert_eq!(all.len(), 100);
+
+        for &id in &all[..90] {
+            assert!(index.remove(id).is_some());
+            assert_eq!(index.remove(id), None);
+        }
+        assert_eq!(index.len(), 10);
+        assert_eq!(ids(index.query(&rect(0, 0, 1000, 1000))), all[90..]);
+    }
+
+    #[test]
+    fn nearest_goes_by_distance_to_the_edge() {
+        let mut index = SpatialIndex::new();
+        let far = index.insert(rect(100, 100, 10, 10), ());
+        let inside = index.insert(rect(0, 0, 20, 20), ());
+        let right = index.insert(rect(25, 5, 5, 5), ());
+        let below = index.insert(rect(5, 25, 5, 5), ());
+
+        let point = Point::new(18, 8);
+        assert_eq!(ids(index.nearest(point, 2)), [inside, right]);
+        assert_eq!(ids(index.nearest(point, 10)), [inside, right, below, far]);
+        assert_eq!(ids(index.nearest(point, 0)), []);
+        // Both 5 away, so the lower id wins.
+        assert_eq!(
+            ids(index.nearest(Point::new(25, 25), 4))[1..3],
+            [right, below]
+        );
+        assert_eq!(ids(SpatialIndex::<()>::new().nearest(point, 1)), []);
+    }
+
+    #[test]
+    fn overlapping_pairs_are_listed_once() {
+        let index: SpatialIndex<()> = [
+            rect(0, 0, 10, 10),
+            rect(5, 5, 10, 10),
+            rect(10, 0, 5, 5),
+            rect(20, 20, 1, 1),
+            rect(0, 0, 30, 30),
+        ]
+        .into_iter()
+        .map(|rect| (rect, ()))
+        .collect();
+
+        let pairs: Vec<(u64, u64)> = index
+            .overlapping_pairs()
+            .into_iter()
+            .map(|(a, b)| (a.0, b.0))
+            .collect();
+        assert_eq!(pairs, [(0, 1), (0, 4), (1, 4), (2, 4), (3, 4)]);
+    }
+
+    fn rects() -> impl Strategy<Value = Vec<Rect>> {
+        // Mostly small rectangles in a small area, so that they overlap and nodes split, with
+        // a few anywhere on the grid.
+        let near = (0..200u32, 0..200u32, 0..30u32, 0..30u32);
+        let anywhere = (any::<u32>(), any::<u32>(), any::<u32>(), any::<u32>());
+        let rect = prop_oneof![
+            9 => near,
+            1 => anywhere,
+        ]
+        .prop_map(|(x, y, width, height)| {
+            rect(x, y, width.min(u32::MAX - x), height.min(u32::MAX - y))
+        });
+        prop::collection::vec(rect, 0..120)
+    }
+
+    proptest! {
+        #[test]
+        fn finds_what_a_scan_finds(
+            rects in rects(),
+            removals in prop::collection::vec(any::<prop::sample::Index>(), 0..40),
+            window in (0..250u32, 0..250u32, 0..80u32, 0..80u32),
+            point in (0..250u32, 0..250u32),
+        ) {
+            let mut index = SpatialIndex::new();
+            let mut all: Vec<(Id, Rect)> =
+                rects.iter().map(|&rect| (index.insert(rect, ()), rect)).collect();
+            for removal in removals {
+                if all.is_empty() {
+                    break;
+                }
+                let (id, rect) = all.remove(removal.index(all.len()));
+                prop_assert_eq!(index.remove(id), Some((rect, ())));
+            }
+            prop_assert_eq!(index.len(), all.len());
+
+            let (x, y, width, height) = window;
+            let window = rect(x, y, width, height);
+            prop_assert_eq!(ids(index.query(&window)), scan(&all, |r| r.intersects(&window)));
+
+            let point = Point::new(point.0, point.1);
+            prop_assert_eq!(ids(index.at_point(point)), scan(&all, |r| r.contains_point(point)));
+
+            let mut by_distance = all.clone();
+            by_distance.sort_by_key(|&(id, rect)| (distance_squared(point, &rect), id));
+            let nearest: Vec<Id> = by_distance.iter().take(5).map(|&(id, _)| id).collect();
+            prop_assert_eq!(ids(index.nearest(point, 5)), nearest);
+
+            let mut pairs = Vec::new();
+            for (i, &(a, rect)) in all.iter().enumerate() {
+                for &(b, other) in &all[i + 1..] {
+                    if rect.intersects(&other) {
+                        pairs.push((a, b));
+                    }
+                }
+            }
+            prop_assert_eq!(index.overlapping_pairs(), pairs);
+        }
+    }
+}
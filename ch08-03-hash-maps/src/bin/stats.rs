@@ -0,0 +1,281 @@
+use ch08_03_hash_maps::statistics::{
+    self, StatsError,
+    descriptive::{self, Interpolation, Summary},
+    input::{self, Column, Format},
+};
+use std::{
+    env, fs,
+    io::{self, BufReader},
+    process,
+};
+
+const USAGE: &str = "\
+Usage: stats [options] [FILE...]
+
+Reads numbers separated by newlines, commas or whitespace from each FILE,
+or from stdin when no FILE is given or FILE is '-', and prints statistics about them.
+
+Options:
+  --column <n|name>   read only this CSV column, by 1-based number or header name
+  --skip-header       ignore the first non-blank line of each input
+  --stats <list>      comma-separated statistics to report (default: all but mode)
+                      count, mean, variance, std_dev, min, q1, median, q3, max,
+                      range, mode, or pN for the Nth percentile (e.g. p90)
+  --json              print a JSON object instead of a table
+  --help              show this message";
+
+const DEFAULT_STATS: [Stat; 10] = [
+    Stat::Count,
+    Stat::Mean,
+    Stat::Variance,
+    Stat::StdDev,
+    Stat::Min,
+    Stat::Q1,
+    Stat::Median,
+    Stat::Q3,
+    Stat::Max,
+    Stat::Range,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stat {
+    Count,
+    Mean,
+    Variance,
+    StdDev,
+    Min,
+    Q1,
+    Median,
+    Q3,
+    Max,
+    Range,
+    Mode,
+    Percentile(f64),
+}
+
+impl Stat {
+    fn parse(name: &str) -> Option<Stat> {
+        let stat = match name {
+            "count" => Stat::Count,
+            "mean" => Stat::Mean,
+            "variance" => Stat::Variance,
+            "std_dev" => Stat::StdDev,
+            "min" => Stat::Min,
+            "q1" => Stat::Q1,
+            "median" => Stat::Median,
+            "q3" => Stat::Q3,
+            "max" => Stat::Max,
+            "range" => Stat::Range,
+            "mode" => Stat::Mode,
+            _ => {
+                let p: f64 = name.strip_prefix('p')?.parse().ok()?;
+                if !(0.0..=100.0).contains(&p) {
+                    return None;
+                }
+                Stat::Percentile(p)
+            }
+        };
+        Some(stat)
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Stat::Count => "count".to_string(),
+            Stat::Mean => "mean".to_string(),
+            Stat::Variance => "variance".to_string(),
+            Stat::StdDev => "std_dev".to_string(),
+            Stat::Min => "min".to_string(),
+            Stat::Q1 => "q1".to_string(),
+            Stat::Median => "median".to_string(),
+            Stat::Q3 => "q3".to_string(),
+            Stat::Max => "max".to_string(),
+            Stat::Range => "range".to_string(),
+            Stat::Mode => "mode".to_string(),
+            Stat::Percentile(p) => format!("p{p}"),
+        }
+    }
+
+    // Variance and standard deviation of a single value are reported as missing rather than failing.
+    fn compute(&self, summary: &Summary, nums: &[f64]) -> Result<Vec<Option<f64>>, StatsError> {
+        let value = match self {
+            Stat::Count => Some(summary.count as f64),
+            Stat::Mean => Some(summary.mean),
+            Stat::Variance => summary.variance,
+            Stat::StdDev => summary.std_dev,
+            Stat::Min => Some(summary.min),
+            Stat::Q1 => Some(summary.q1),
+            Stat::Median => Some(summary.median),
+            Stat::Q3 => Some(summary.q3),
+            Stat::Max => Some(summary.max),
+            Stat::Range => Some(summary.range),
+            Stat::Mode => {
+                let modes = statistics::modes_f64(nums)?;
+                return Ok(modes.values.into_iter().map(Some).collect());
+            }
+            Stat::Percentile(p) => Some(descriptive::percentile(nums, *p, Interpolation::Linear)?),
+        };
+        Ok(vec![value])
+    }
+}
+
+struct Options {
+    files: Vec<String>,
+    format: Format,
+    stats: Vec<Stat>,
+    json: bool,
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1));
+
+    let mut nums = Vec::new();
+    for file in &options.files {
+        let result = if file == "-" {
+            input::read_numbers(io::stdin().lock(), &options.format)
+        } else {
+            match fs::File::open(file) {
+                Ok(opened) => input::read_numbers(BufReader::new(opened), &options.format),
+                Err(error) => fail(&format!("could not open {file}: {error}")),
+            }
+        };
+
+        match result {
+            Ok(read) => nums.extend(read),
+            Err(error) => {
+                let source = if file == "-" { "stdin" } else { file };
+                fail(&format!("{source}: {error}"));
+            }
+        }
+    }
+
+    let summary = match Summary::from_slice(&nums) {
+        Ok(summary) => summary,
+        Err(error) => fail(&error.to_string()),
+    };
+
+    let mut report = Vec::new();
+    for stat in &options.stats {
+        match stat.compute(&summary, &nums) {
+            Ok(values) => report.push((stat.name(), values)),
+            Err(error) => fail(&format!("{}: {error}", stat.name())),
+        }
+    }
+
+    if options.json {
+        println!("{}", to_json(&report));
+    } else {
+        print!("{}", to_table(&report));
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {message}");
+    process::exit(1);
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Options {
+    let mut options = Options {
+        files: Vec::new(),
+        format: Format::default(),
+        stats: DEFAULT_STATS.to_vec(),
+        json: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            "--json" => options.json = true,
+            "--skip-header" => options.format.skip_header = true,
+            "--column" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--column needs a value"));
+                options.format.column = Some(match value.parse() {
+                    Ok(0) => usage_error("--column numbers start at 1"),
+                    Ok(index) => Column::Index(index),
+                    Err(_) => Column::Name(value),
+                });
+            }
+            "--stats" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--stats needs a value"));
+                options.stats = value
+                    .split(',')
+                    .map(|name| {
+                        Stat::parse(name.trim()).unwrap_or_else(|| {
+                            usage_error(&format!("unknown statistic '{}'", name.trim()))
+                        })
+                    })
+                    .collect();
+            }
+            flag if flag.starts_with("--") => usage_error(&format!("unexpected argument '{flag}'")),
+            _ => options.files.push(arg),
+        }
+    }
+
+    if options.files.is_empty() {
+        options.files.push("-".to_string());
+    }
+    options
+}
+
+fn format_value(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("{value:.4}"),
+        None => "n/a".to_string(),
+    }
+}
+
+fn to_table(report: &[(String, Vec<Option<f64>>)]) -> String {
+    let mut table = String::new();
+
+    for (name, values) in report {
+        let value = match values.as_slice() {
+            [Some(count)] if name == "count" => count.to_string(),
+            [value] => format_value(*value),
+            values => values
+                .iter()
+                .map(|value| format_value(*value))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        table.push_str(&format!("{name:<10}{value:>14}\n"));
+    }
+
+    table
+}
+
+// Same layout as `Summary::to_json`: missing and non-finite values become null and a
+// statistic with several values, like a tied mode, becomes an array.
+fn to_json(report: &[(String, Vec<Option<f64>>)]) -> String {
+    let json_value = |value: &Option<f64>| match value {
+        Some(value) if value.is_finite() => value.to_string(),
+        _ => "null".to_string(),
+    };
+
+    let fields: Vec<String> = report
+        .iter()
+        .map(|(name, values)| {
+            let value = match (name.as_str(), values.as_slice()) {
+                ("mode", values) => format!(
+                    "[{}]",
+                    values.iter().map(json_value).collect::<Vec<_>>().join(", ")
+                ),
+                (_, [value]) => json_value(value),
+                _ => "null".to_string(),
+            };
+            format!("\"{name}\": {value}")
+        })
+        .collect();
+
+    format!("{{{}}}", fields.join(", "))
+}
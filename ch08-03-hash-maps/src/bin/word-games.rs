@@ -0,0 +1,63 @@
+use ch08_03_hash_maps::word_games::{Chain, TRANSFORM_NAMES};
+use std::{
+    env,
+    io::{self, BufRead, BufWriter, Write},
+    process,
+};
+
+const USAGE: &str = "\
+Usage: word-games <transform>[,<transform>...] [<transform>...]
+
+Copies stdin to stdout, passing every line through the transforms in the order given.
+Punctuation and whitespace are left where they are.
+
+Transforms: pig-latin, ubbi-dubbi, rot13, reverse
+
+Example: echo 'Hello, world!' | word-games pig-latin rot13";
+
+fn main() {
+    let mut names = Vec::new();
+    for arg in env::args().skip(1) {
+        if arg == "--help" || arg == "-h" {
+            println!("{USAGE}");
+            return;
+        }
+        names.extend(arg.split(',').map(str::to_string));
+    }
+    if names.is_empty() {
+        usage_error(&format!(
+            "name at least one transform ({})",
+            TRANSFORM_NAMES.join(", ")
+        ));
+    }
+
+    let chain = match Chain::from_names(&names) {
+        Ok(chain) => chain,
+        Err(error) => usage_error(&error.to_string()),
+    };
+
+    if let Err(error) = run(&chain, io::stdin().lock(), io::stdout().lock()) {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+// Works a line at a time so arbitrarily long input never has to fit in memory,
+// and flushes after each line so interactive use shows results straight away.
+fn run<R: BufRead, W: Write>(chain: &Chain, mut input: R, output: W) -> io::Result<()> {
+    let mut output = BufWriter::new(output);
+    let mut line = String::new();
+
+    while input.read_line(&mut line)? > 0 {
+        output.write_all(chain.apply(&line).as_bytes())?;
+        output.flush()?;
+        line.clear();
+    }
+
+    Ok(())
+}
@@ -1,63 +1,84 @@
-use std::{collections::HashMap, io};
+use std::{io, path::Path};
+
+pub mod command;
+pub mod directory;
+pub mod employee;
+pub mod history;
+mod index;
+pub mod org_chart;
+pub mod query;
+pub mod repl;
+pub mod storage;
+
+pub use command::{Command, ParseError};
+pub use directory::{Directory, DirectoryError};
+pub use employee::{Employee, EmployeeId, EmployeeRef};
+pub use history::History;
+pub use query::Query;
+pub use storage::{LoadStatus, StorageError};
 
 pub fn employee_directory_program() {
     // Using a hash map and vectors, create a text interface to allow a user to add employee names to a department in a company;
     // for example, “Add Sally to Engineering” or “Add Amir to Sales.”
     // Then, let the user retrieve a list of all people in a department or all people in the company by department, sorted alphabetically.
 
-    let mut company: HashMap<String, Vec<String>> = HashMap::new();
-
-    loop {
-        println!("> ");
-
-        let mut input = String::new();
-
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-
-        let parts: Vec<&str> = input.split_whitespace().collect();
-
-        match parts.as_slice() {
-            ["Add", name, "to", department] => {
-                company
-                    .entry(department.to_string())
-                    .or_insert_with(Vec::new)
-                    .push(name.to_string());
-
-                println!("Added {name} to {department}");
-            }
-
-            ["List", department] if *department != "All" => {
-                if let Some(employees) = company.get(*department) {
-                    let mut sorted_employees = employees.clone();
-                    sorted_employees.sort();
-
-                    println!("{department}:");
-                    for name in sorted_employees {
-                        println!("    {name}")
-                    }
-                } else {
-                    println!("No such department")
-                }
-            }
-
-            ["List", "All"] => {
-                let mut departments: Vec<_> = company.keys().collect();
-                departments.sort();
-
-                for dept in departments {
-                    let mut employees = company[dept].clone(); // we used direct call instead of get because we know dept definitely exists
-                    employees.sort();
+    // The parsing lives in `command`, the data in `directory` and the loop in `repl`,
+    // so each can be tested without a terminal.
+    // The directory is kept in a file next to where the program is run and saved after every change,
+    // along with a log of the changes that Undo, Redo and History work from. The log is the full
+    // record, so the directory is replayed from it when there is one.
+    let path = Path::new(storage::DEFAULT_DIRECTORY_FILE);
+    let log = history::log_path(path);
+    let mut history = match History::load(&log) {
+        Ok((history, LoadStatus::Loaded)) => {
+            println!(
+                "Loaded the directory and its history from {}",
+                log.display()
+            );
+            history
+        }
+        Ok((_, LoadStatus::Missing)) => History::new(load_directory(path)),
+        Ok((_, LoadStatus::Recovered { backup })) => {
+            println!(
+                "{} could not be replayed; it was moved to {} and a new history starts",
+                log.display(),
+                backup.display()
+            );
+            History::new(load_directory(path))
+        }
+        Err(error) => {
+            println!("Could not load {}: {error}", log.display());
+            History::new(load_directory(path))
+        }
+    };
 
-                    println!("{dept}:");
-                    for name in employees {
-                        println!("    {name}")
-                    }
-                }
-            }
+    repl::run(
+        io::stdin().lock(),
+        io::stdout().lock(),
+        &mut history,
+        Some(path),
+    )
+    .expect("Failed to read line");
+}
 
-            _ => println!("Invalid command"),
+fn load_directory(path: &Path) -> Directory {
+    match Directory::load(path) {
+        Ok((directory, LoadStatus::Missing)) => directory,
+        Ok((directory, LoadStatus::Loaded)) => {
+            println!("Loaded the directory from {}", path.display());
+            directory
+        }
+        Ok((directory, LoadStatus::Recovered { backup })) => {
+            println!(
+                "{} could not be read; it was moved to {} and the directory starts empty",
+                path.display(),
+                backup.display()
+            );
+            directory
+        }
+        Err(error) => {
+            println!("Could not load {}: {error}", path.display());
+            Directory::new()
         }
     }
 }
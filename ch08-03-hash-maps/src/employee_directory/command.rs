@@ -0,0 +1,805 @@
+use std::{fmt, str::FromStr};
+
+use super::{
+    employee::{Date, Details, EmployeeId, EmployeeRef, normalize},
+    org_chart::ChartFormat,
+    query::{Condition, FIELD_NAMES, Field, Op, Query, Scope, Sort},
+};
+
+// One line of input to the employee directory, already checked for the right shape.
+// Command words and keywords such as "to" or "where" are case-insensitive; names are kept as typed.
+//
+// Names and departments may be several words long. A word with double quotes in it is never
+// taken as a keyword, so `Add "Anne to Be" to Sales` works; the quotes themselves are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    // Add <name> to <department> [title=<title>] [email=<email>] [start_date=YYYY-MM-DD]
+    Add {
+        name: String,
+        department: String,
+        details: Details,
+    },
+    // Remove <employee> [from <department>]
+    Remove {
+        employee: EmployeeRef,
+    },
+    // Move <employee> [from <department>] to <department>
+    Move {
+        employee: EmployeeRef,
+        to: String,
+    },
+    // Show <employee> [in <department>]
+    Show {
+        employee: EmployeeRef,
+    },
+    // Set manager of <department> to <employee>
+    SetManager {
+        department: String,
+        manager: EmployeeRef,
+    },
+    // Unset manager of <department>
+    UnsetManager {
+        department: String,
+    },
+    // Chart [<department>] [as text|dot]
+    Chart {
+        department: Option<String>,
+        format: ChartFormat,
+    },
+    // List <department>|All [where <condition> [and <condition>]...] [sort by <field> [asc|desc]]
+    List(Query),
+    // Count [by <field>]
+    Count {
+        by: Option<Field>,
+    },
+    // Find <start of a name>
+    Find {
+        prefix: String,
+    },
+    // Import <file.csv|file.json>
+    Import {
+        path: String,
+    },
+    // Export <file.csv|file.json>
+    Export {
+        path: String,
+    },
+    Undo,
+    Redo,
+    // History: every change made, when, and what was undone.
+    History,
+    Quit,
+    Help,
+}
+
+pub const HELP: &str = "\
+Commands:
+  Add <name> to <department> [title=<title>] [email=<email>] [start_date=YYYY-MM-DD]
+  Remove <employee> [from <department>]
+  Move <employee> [from <department>] to <department>
+  Show <employee> [in <department>]
+  Set manager of <department> to <employee>
+  Unset manager of <department>
+  Chart [<department>] [as text|dot]
+  List <department>|All [where <condition> [and <condition>]...] [sort by <field> [asc|desc]]
+  Count [by <field>]
+  Find <start of a name>
+  Import <file.csv|file.json>
+  Export <file.csv|file.json>
+  Undo
+  Redo
+  History
+  Help
+  Quit
+An <employee> is a name or an ID such as #3. Put names that contain a keyword in quotes,
+as in: Add \"Anne to Be\" to \"Human Resources\" title=\"Head of People\"
+Departments nest with slashes, as in Engineering/Platform/Storage, and can be named by
+their last part when that is unique, as in Platform. A leading / means the full path.
+A <field> is id, name, department, title, email or start_date, and a <condition> compares
+one with =, !=, <, <=, > or >=, as in: List All where start_date>=2020-01-01 sort by name";
+
+const LIST_USAGE: &str =
+    "List <department>|All [where <condition> [and <condition>]...] [sort by <field> [asc|desc]]";
+
+const ADD_USAGE: &str =
+    "Add <name> to <department> [title=<title>] [email=<email>] [start_date=YYYY-MM-DD]";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownCommand(String),
+    // The command word was recognised but the rest of the line did not fit its usage.
+    Usage {
+        command: &'static str,
+        usage: &'static str,
+    },
+    UnclosedQuote,
+    UnknownField {
+        field: String,
+        expected: &'static str,
+    },
+    InvalidField {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty command, type Help to see the commands"),
+            ParseError::UnknownCommand(word) => {
+                write!(f, "unknown command '{word}', type Help to see the commands")
+            }
+            ParseError::Usage { command, usage } => {
+                write!(f, "{command} expects '{usage}'")
+            }
+            ParseError::UnclosedQuote => write!(f, "a quote is never closed"),
+            ParseError::UnknownField { field, expected } => {
+                write!(f, "unknown field '{field}', expected {expected}")
+            }
+            ParseError::InvalidField { field, message } => write!(f, "{field}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn usage(command: &'static str, usage: &'static str) -> ParseError {
+    ParseError::Usage { command, usage }
+}
+
+// One whitespace-separated word of a command line, with any quotes removed.
+// `bare` is how many bytes at the start came before the first quote.
+#[derive(Debug, Default)]
+struct Word {
+    text: String,
+    bare: usize,
+    quoted: bool,
+}
+
+impl Word {
+    fn is_keyword(&self, expected: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(expected)
+    }
+
+    // `key<op>value` with one of the `Op` symbols, where the key and the operator come
+    // before any quote.
+    fn comparison(&self) -> Option<(&str, &str, &str)> {
+        let bare = &self.text[..self.bare];
+        let start = bare.find(['=', '!', '<', '>'])?;
+        let key = &bare[..start];
+        let is_key = !key.is_empty() && key.bytes().all(|b| b.is_ascii_alphabetic() || b == b'_');
+        let op = ["!=", "<=", ">=", "=", "<", ">"]
+            .into_iter()
+            .find(|op| bare[start..].starts_with(op))?;
+        is_key.then(|| (key, op, &self.text[start + op.len()..]))
+    }
+
+    // `key=value`
+    fn field(&self) -> Option<(&str, &str)> {
+        match self.comparison()? {
+            (key, "=", value) => Some((key, value)),
+            _ => None,
+        }
+    }
+}
+
+fn split_words(s: &str) -> Result<Vec<Word>, ParseError> {
+    let mut words = Vec::new();
+    let mut word: Option<Word> = None;
+    let mut in_quotes = false;
+
+    for c in s.chars() {
+        if c.is_whitespace() && !in_quotes {
+            words.extend(word.take());
+            continue;
+        }
+
+        let word = word.get_or_insert_with(Word::default);
+        if c == '"' {
+            word.quoted = true;
+            in_quotes = !in_quotes;
+        } else {
+            word.text.push(c);
+            if !word.quoted {
+                word.bare = word.text.len();
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(ParseError::UnclosedQuote);
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+// The words joined back into one name, or None if there are none.
+fn join(words: &[Word]) -> Option<String> {
+    let text: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+    Some(normalize(&text.join(" "))).filter(|text| !text.is_empty())
+}
+
+fn split_at_keyword<'a>(words: &'a [Word], keyword: &str) -> Option<(&'a [Word], &'a [Word])> {
+    let index = words.iter().position(|word| word.is_keyword(keyword))?;
+    Some((&words[..index], &words[index + 1..]))
+}
+
+// "#3", "<name>" or "<name> <keyword> <department>".
+fn employee_ref(words: &[Word], keyword: &str) -> Option<EmployeeRef> {
+    if let [word] = words
+        && !word.quoted
+        && let Ok(id) = word.text.parse::<EmployeeId>()
+    {
+        return Some(EmployeeRef::Id(id));
+    }
+
+    match split_at_keyword(words, keyword) {
+        Some((name, department)) => Some(EmployeeRef::Name {
+            name: join(name)?,
+            department: Some(join(department)?),
+        }),
+        None => Some(EmployeeRef::Name {
+            name: join(words)?,
+            department: None,
+        }),
+    }
+}
+
+// The department followed by any number of `key=value` fields.
+fn department_and_details(words: &[Word]) -> Result<(String, Details), ParseError> {
+    let first_field = words
+        .iter()
+        .position(|word| word.field().is_some())
+        .unwrap_or(words.len());
+    let department = join(&words[..first_field]).ok_or(usage("Add", ADD_USAGE))?;
+
+    let mut details = Details::default();
+    for word in &words[first_field..] {
+        let (key, value) = word.field().ok_or(usage("Add", ADD_USAGE))?;
+        let value = normalize(value);
+        match key.to_ascii_lowercase().as_str() {
+            "title" => details.title = Some(value).filter(|title| !title.is_empty()),
+            "email" => {
+                if value.contains(' ') || !value.contains('@') {
+                    return Err(ParseError::InvalidField {
+                        field: "email",
+                        message: format!("'{value}' is not an email address"),
+                    });
+                }
+                details.email = Some(value);
+            }
+            "start_date" => {
+                let date = value
+                    .parse::<Date>()
+                    .map_err(|error| ParseError::InvalidField {
+                        field: "start_date",
+                        message: error.to_string(),
+                    })?;
+                details.start_date = Some(date);
+            }
+            _ => {
+                return Err(ParseError::UnknownField {
+                    field: key.to_string(),
+                    expected: "title, email or start_date",
+                });
+            }
+        }
+    }
+
+    Ok((department, details))
+}
+
+fn query_field(name: &str) -> Result<Field, ParseError> {
+    Field::from_name(name).ok_or_else(|| ParseError::UnknownField {
+        field: name.to_string(),
+        expected: FIELD_NAMES,
+    })
+}
+
+// The first word holds the field and operator; the value may run on over the other words,
+// so `title=Head of People` works without quotes.
+fn condition(words: &[Word]) -> Result<Condition, ParseError> {
+    let (first, rest) = words.split_first().ok_or(usage("List", LIST_USAGE))?;
+    let (key, op, value) = first.comparison().ok_or(usage("List", LIST_USAGE))?;
+    let field = query_field(key)?;
+    let value: Vec<&str> = std::iter::once(value)
+        .chain(rest.iter().map(|word| word.text.as_str()))
+        .collect();
+
+    Ok(Condition {
+        field,
+        op: Op::from_symbol(op).expect("comparison only returns known operators"),
+        value: field
+            .parse_value(&value.join(" "))
+            .map_err(|message| ParseError::InvalidField {
+                field: field.name(),
+                message,
+            })?,
+    })
+}
+
+// `<department>|All [where <condition> [and <condition>]...] [sort by <field> [asc|desc]]`
+fn list_query(words: &[Word]) -> Result<Query, ParseError> {
+    let (words, sort) = match words.iter().position(|word| word.is_keyword("sort")) {
+        Some(index) => (&words[..index], Some(&words[index + 1..])),
+        None => (words, None),
+    };
+    let (scope, conditions) = match split_at_keyword(words, "where") {
+        Some((scope, conditions)) => (scope, Some(conditions)),
+        None => (words, None),
+    };
+
+    let scope = match scope {
+        [all] if all.is_keyword("all") => Scope::All,
+        _ => Scope::Department(join(scope).ok_or(usage("List", LIST_USAGE))?),
+    };
+    let mut query = Query::new(scope);
+
+    if let Some(conditions) = conditions {
+        for condition_words in conditions.split(|word| word.is_keyword("and")) {
+            query.conditions.push(condition(condition_words)?);
+        }
+    }
+
+    query.sort = match sort {
+        None => None,
+        Some([by, field, rest @ ..]) if by.is_keyword("by") => {
+            let descending = match rest {
+                [] => false,
+                [order] if order.is_keyword("asc") => false,
+                [order] if order.is_keyword("desc") => true,
+                _ => return Err(usage("List", LIST_USAGE)),
+            };
+            Some(Sort {
+                field: query_field(&field.text)?,
+                descending,
+            })
+        }
+        Some(_) => return Err(usage("List", LIST_USAGE)),
+    };
+
+    Ok(query)
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = split_words(s)?;
+        let Some((first, rest)) = words.split_first() else {
+            return Err(ParseError::Empty);
+        };
+
+        match first.text.to_ascii_lowercase().as_str() {
+            "add" => {
+                let (name, department) =
+                    split_at_keyword(rest, "to").ok_or(usage("Add", ADD_USAGE))?;
+                let name = join(name).ok_or(usage("Add", ADD_USAGE))?;
+                let (department, details) = department_and_details(department)?;
+                Ok(Command::Add {
+                    name,
+                    department,
+                    details,
+                })
+            }
+            "remove" => match employee_ref(rest, "from") {
+                Some(employee) => Ok(Command::Remove { employee }),
+                None => Err(usage("Remove", "Remove <employee> [from <department>]")),
+            },
+            "move" => {
+                let command = split_at_keyword(rest, "to").and_then(|(employee, to)| {
+                    Some(Command::Move {
+                        employee: employee_ref(employee, "from")?,
+                        to: join(to)?,
+                    })
+                });
+                command.ok_or(usage(
+                    "Move",
+                    "Move <employee> [from <department>] to <department>",
+                ))
+            }
+            "show" => match employee_ref(rest, "in") {
+                Some(employee) => Ok(Command::Show { employee }),
+                None => Err(usage("Show", "Show <employee> [in <department>]")),
+            },
+            "set" => {
+                let set_usage = || usage("Set", "Set manager of <department> to <employee>");
+                let [manager, of, rest @ ..] = rest else {
+                    return Err(set_usage());
+                };
+                if !manager.is_keyword("manager") || !of.is_keyword("of") {
+                    return Err(set_usage());
+                }
+                let (department, employee) = split_at_keyword(rest, "to").ok_or_else(set_usage)?;
+                Ok(Command::SetManager {
+                    department: join(department).ok_or_else(set_usage)?,
+                    manager: employee_ref(employee, "in").ok_or_else(set_usage)?,
+                })
+            }
+            "unset" => match rest {
+                [manager, of, department @ ..]
+                    if manager.is_keyword("manager") && of.is_keyword("of") =>
+                {
+                    match join(department) {
+                        Some(department) => Ok(Command::UnsetManager { department }),
+                        None => Err(usage("Unset", "Unset manager of <department>")),
+                    }
+                }
+                _ => Err(usage("Unset", "Unset manager of <department>")),
+            },
+            "chart" => {
+                let (department, format) = match rest {
+                    [department @ .., as_word, format] if as_word.is_keyword("as") => {
+                        let format = if format.is_keyword("text") {
+                            ChartFormat::Text
+                        } else if format.is_keyword("dot") {
+                            ChartFormat::Dot
+                        } else {
+                            return Err(usage("Chart", "Chart [<department>] [as text|dot]"));
+                        };
+                        (department, format)
+                    }
+                    _ => (rest, ChartFormat::Text),
+                };
+                Ok(Command::Chart {
+                    department: join(department),
+                    format,
+                })
+            }
+            "list" => list_query(rest).map(Command::List),
+            "count" => match rest {
+                [] => Ok(Command::Count { by: None }),
+                [by, field] if by.is_keyword("by") => Ok(Command::Count {
+                    by: Some(query_field(&field.text)?),
+                }),
+                _ => Err(usage("Count", "Count [by <field>]")),
+            },
+            "find" => match join(rest) {
+                Some(prefix) => Ok(Command::Find { prefix }),
+                None => Err(usage("Find", "Find <start of a name>")),
+            },
+            "import" => match rest {
+                [path] => Ok(Command::Import {
+                    path: path.text.clone(),
+                }),
+                _ => Err(usage("Import", "Import <file.csv|file.json>")),
+            },
+            "export" => match rest {
+                [path] => Ok(Command::Export {
+                    path: path.text.clone(),
+                }),
+                _ => Err(usage("Export", "Export <file.csv|file.json>")),
+            },
+            "undo" if rest.is_empty() => Ok(Command::Undo),
+            "redo" if rest.is_empty() => Ok(Command::Redo),
+            "history" if rest.is_empty() => Ok(Command::History),
+            "quit" | "exit" if rest.is_empty() => Ok(Command::Quit),
+            "help" if rest.is_empty() => Ok(Command::Help),
+            "undo" => Err(usage("Undo", "Undo")),
+            "redo" => Err(usage("Redo", "Redo")),
+            "history" => Err(usage("History", "History")),
+            "quit" | "exit" => Err(usage("Quit", "Quit")),
+            "help" => Err(usage("Help", "Help")),
+            _ => Err(ParseError::UnknownCommand(first.text.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn by_name(name: &str, department: Option<&str>) -> EmployeeRef {
+        EmployeeRef::Name {
+            name: name.to_string(),
+            department: department.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(
+            "Add Sally to Engineering".parse(),
+            Ok(Command::Add {
+                name: "Sally".to_string(),
+                department: "Engineering".to_string(),
+                details: Details::default()
+            })
+        );
+        assert_eq!(
+            "remove Amir FROM Sales".parse(),
+            Ok(Command::Remove {
+                employee: by_name("Amir", Some("Sales"))
+            })
+        );
+        assert_eq!(
+            "Move Amir from Sales to Engineering".parse(),
+            Ok(Command::Move {
+                employee: by_name("Amir", Some("Sales")),
+                to: "Engineering".to_string()
+            })
+        );
+        assert_eq!(
+            "Move #4 to Engineering".parse(),
+            Ok(Command::Move {
+                employee: EmployeeRef::Id(EmployeeId(4)),
+                to: "Engineering".to_string()
+            })
+        );
+        assert_eq!(
+            "Show Sally in Engineering".parse(),
+            Ok(Command::Show {
+                employee: by_name("Sally", Some("Engineering"))
+            })
+        );
+        assert_eq!(
+            "  List   Sales \n".parse(),
+            Ok(Command::List(Query::new(Scope::Department(
+                "Sales".to_string()
+            ))))
+        );
+        assert_eq!(
+            "List all".parse(),
+            Ok(Command::List(Query::new(Scope::All)))
+        );
+        assert_eq!("Count".parse(), Ok(Command::Count { by: None }));
+        assert_eq!(
+            "count BY Department".parse(),
+            Ok(Command::Count {
+                by: Some(Field::Department)
+            })
+        );
+        assert_eq!(
+            "Find mary j".parse(),
+            Ok(Command::Find {
+                prefix: "mary j".to_string()
+            })
+        );
+        assert_eq!(
+            "import staff.csv".parse(),
+            Ok(Command::Import {
+                path: "staff.csv".to_string()
+            })
+        );
+        assert_eq!(
+            "Export \"my backup.json\"".parse(),
+            Ok(Command::Export {
+                path: "my backup.json".to_string()
+            })
+        );
+        assert_eq!("undo".parse(), Ok(Command::Undo));
+        assert_eq!("Redo".parse(), Ok(Command::Redo));
+        assert_eq!("HISTORY".parse(), Ok(Command::History));
+        assert_eq!("QUIT".parse(), Ok(Command::Quit));
+        assert_eq!("Help".parse(), Ok(Command::Help));
+    }
+
+    #[test]
+    fn multi_word_and_quoted_names() {
+        assert_eq!(
+            "Add Mary Jane to Human  Resources".parse(),
+            Ok(Command::Add {
+                name: "Mary Jane".to_string(),
+                department: "Human Resources".to_string(),
+                details: Details::default()
+            })
+        );
+        assert_eq!(
+            "Remove \"Anne to Be\" from \"Sales\"".parse(),
+            Ok(Command::Remove {
+                employee: by_name("Anne to Be", Some("Sales"))
+            })
+        );
+        assert_eq!(
+            "List \"All\"".parse(),
+            Ok(Command::List(Query::new(Scope::Department(
+                "All".to_string()
+            ))))
+        );
+        assert_eq!(
+            "Remove \"#3\"".parse(),
+            Ok(Command::Remove {
+                employee: by_name("#3", None)
+            })
+        );
+        assert_eq!(
+            "Add \"Sally to Engineering".parse::<Command>(),
+            Err(ParseError::UnclosedQuote)
+        );
+    }
+
+    #[test]
+    fn parses_fields() {
+        let command: Command = "Add Mary Jane to Human Resources title=\"Head of People\" \
+                                email=mj@example.com start_date=2021-03-01"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Add {
+                name: "Mary Jane".to_string(),
+                department: "Human Resources".to_string(),
+                details: Details {
+                    title: Some("Head of People".to_string()),
+                    email: Some("mj@example.com".to_string()),
+                    start_date: Some(Date::new(2021, 3, 1).unwrap()),
+                }
+            }
+        );
+        assert_eq!(
+            "Add Sally to Sales salary=1"
+                .parse::<Command>()
+                .unwrap_err()
+                .to_string(),
+            "unknown field 'salary', expected title, email or start_date"
+        );
+        assert_eq!(
+            "Add Sally to Sales start_date=soon"
+                .parse::<Command>()
+                .unwrap_err()
+                .to_string(),
+            "start_date: 'soon' is not a date, expected YYYY-MM-DD"
+        );
+        assert!(matches!(
+            "Add Sally to Sales email=sally title=Boss".parse::<Command>(),
+            Err(ParseError::InvalidField { field: "email", .. })
+        ));
+        assert!(matches!(
+            "Add Sally to title=Boss".parse::<Command>(),
+            Err(ParseError::Usage { command: "Add", .. })
+        ));
+    }
+
+    #[test]
+    fn parses_manager_and_chart_commands() {
+        assert_eq!(
+            "Set manager of Engineering/Platform to Amir".parse(),
+            Ok(Command::SetManager {
+                department: "Engineering/Platform".to_string(),
+                manager: by_name("Amir", None)
+            })
+        );
+        assert_eq!(
+            "set MANAGER of Human Resources to Mary Jane in Sales".parse(),
+            Ok(Command::SetManager {
+                department: "Human Resources".to_string(),
+                manager: by_name("Mary Jane", Some("Sales"))
+            })
+        );
+        assert_eq!(
+            "Unset manager of Platform".parse(),
+            Ok(Command::UnsetManager {
+                department: "Platform".to_string()
+            })
+        );
+        assert_eq!(
+            "Chart".parse(),
+            Ok(Command::Chart {
+                department: None,
+                format: ChartFormat::Text
+            })
+        );
+        assert_eq!(
+            "Chart Engineering as DOT".parse(),
+            Ok(Command::Chart {
+                department: Some("Engineering".to_string()),
+                format: ChartFormat::Dot
+            })
+        );
+        assert!(matches!(
+            "Chart as svg".parse::<Command>(),
+            Err(ParseError::Usage {
+                command: "Chart",
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Set manager Platform to Amir".parse::<Command>(),
+            Err(ParseError::Usage { command: "Set", .. })
+        ));
+    }
+
+    #[test]
+    fn parses_queries() {
+        let command: Command =
+            "List Human Resources where title=Head of People and start_date>=2020-01-01 \
+             sort by start_date DESC"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            command,
+            Command::List(Query {
+                scope: Scope::Department("Human Resources".to_string()),
+                conditions: vec![
+                    Condition {
+                        field: Field::Title,
+                        op: Op::Eq,
+                        value: Field::Title.parse_value("head of people").unwrap()
+                    },
+                    Condition {
+                        field: Field::StartDate,
+                        op: Op::Ge,
+                        value: Field::StartDate.parse_value("2020-01-01").unwrap()
+                    },
+                ],
+                sort: Some(Sort {
+                    field: Field::StartDate,
+                    descending: true
+                }),
+            })
+        );
+        assert_eq!(
+            "List All sort by name".parse(),
+            Ok(Command::List(Query {
+                sort: Some(Sort {
+                    field: Field::Name,
+                    descending: false
+                }),
+                ..Query::new(Scope::All)
+            }))
+        );
+        assert_eq!(
+            "List All where salary>1"
+                .parse::<Command>()
+                .unwrap_err()
+                .to_string(),
+            format!("unknown field 'salary', expected {FIELD_NAMES}")
+        );
+        assert!(matches!(
+            "List All where id>soon".parse::<Command>(),
+            Err(ParseError::InvalidField { field: "id", .. })
+        ));
+        for bad in [
+            "List where title=Manager",
+            "List All where",
+            "List All where title",
+            "List All sort name",
+            "List All sort by name sideways",
+        ] {
+            assert!(
+                matches!(
+                    bad.parse::<Command>(),
+                    Err(ParseError::Usage {
+                        command: "List",
+                        ..
+                    })
+                ),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn explains_what_was_wrong() {
+        assert_eq!("".parse::<Command>(), Err(ParseError::Empty));
+        assert_eq!(
+            "Hire Sally".parse::<Command>(),
+            Err(ParseError::UnknownCommand("Hire".to_string()))
+        );
+        assert_eq!(
+            "Add Sally Engineering"
+                .parse::<Command>()
+                .unwrap_err()
+                .to_string(),
+            format!("Add expects '{ADD_USAGE}'")
+        );
+        assert!(matches!(
+            "Move Sally from Sales".parse::<Command>(),
+            Err(ParseError::Usage {
+                command: "Move",
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Remove Sally from".parse::<Command>(),
+            Err(ParseError::Usage {
+                command: "Remove",
+                ..
+            })
+        ));
+    }
+}
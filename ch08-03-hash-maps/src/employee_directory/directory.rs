@@ -0,0 +1,669 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::{
+    employee::{
+        Details, Employee, EmployeeId, EmployeeRef, normalize, normalize_department,
+        parent_departments,
+    },
+    index::{Index, name_key},
+    query::{Field, Query, Scope},
+};
+
+// Employees by ID, grouped into a tree of departments such as "Engineering/Platform".
+//
+// Departments exist only while they, or departments inside them, have employees: removing or
+// moving the last person out removes the department, and its manager, too. Two people may share
+// a name, but not within one department, where the name alone has to say who is meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory {
+    employees: BTreeMap<EmployeeId, Employee>,
+    index: Index,
+    // Department path to the employee managing it, who may work anywhere in the directory.
+    managers: BTreeMap<String, EmployeeId>,
+    next_id: EmployeeId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectoryError {
+    NoSuchDepartment(String),
+    // A short department name such as "Platform" fits more than one path.
+    AmbiguousDepartment {
+        name: String,
+        paths: Vec<String>,
+    },
+    NoSuchEmployee {
+        name: String,
+        department: String,
+    },
+    NoSuchId(EmployeeId),
+    NoSuchName(String),
+    // More than one person has the name and no department was given to pick one.
+    Ambiguous {
+        name: String,
+        ids: Vec<EmployeeId>,
+    },
+    Duplicate {
+        name: String,
+        department: String,
+        id: EmployeeId,
+    },
+    DuplicateId(EmployeeId),
+    // An employee has the largest possible ID, so there is none left to hand out after them.
+    IdOverflow(EmployeeId),
+}
+
+impl fmt::Display for DirectoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirectoryError::NoSuchDepartment(department) => {
+                write!(f, "there is no department called {department}")
+            }
+            DirectoryError::AmbiguousDepartment { name, paths } => write!(
+                f,
+                "{name} could be any of {}, give the full path",
+                paths.join(", ")
+            ),
+            DirectoryError::NoSuchEmployee { name, department } => {
+                write!(f, "{name} does not work in {department}")
+            }
+            DirectoryError::NoSuchId(id) => write!(f, "there is no employee {id}"),
+            DirectoryError::NoSuchName(name) => write!(f, "there is nobody called {name}"),
+            DirectoryError::Ambiguous { name, ids } => {
+                let ids: Vec<String> = ids.iter().map(EmployeeId::to_string).collect();
+                write!(
+                    f,
+                    "there are several people called {name} ({}), give an ID or a department",
+                    ids.join(", ")
+                )
+            }
+            DirectoryError::Duplicate {
+                name,
+                department,
+                id,
+            } => write!(f, "{name} already works in {department} as {id}"),
+            DirectoryError::DuplicateId(id) => write!(f, "{id} is used more than once"),
+            DirectoryError::IdOverflow(id) => write!(f, "there are no IDs left after {id}"),
+        }
+    }
+}
+
+impl std::error::Error for DirectoryError {}
+
+impl Default for Directory {
+    fn default() -> Directory {
+        Directory {
+            employees: BTreeMap::new(),
+            index: Index::default(),
+            managers: BTreeMap::new(),
+            next_id: EmployeeId(1),
+        }
+    }
+}
+
+impl Directory {
+    pub fn new() -> Directory {
+        Directory::default()
+    }
+
+    pub fn add(&mut self, name: &str, department: &str) -> Result<EmployeeId, DirectoryError> {
+        self.add_with(name, department, Details::default())
+    }
+
+    // The department is looked up as `resolve_department` does, and created if it does not
+    // exist yet. Adding a name that is already in the department (ignoring case) is refused
+    // rather than creating a second, ambiguous entry.
+    pub fn add_with(
+        &mut self,
+        name: &str,
+        department: &str,
+        details: Details,
+    ) -> Result<EmployeeId, DirectoryError> {
+        let id = self.next_id;
+        self.insert(Employee {
+            id,
+            name: normalize(name),
+            department: self.target_department(department)?,
+            details,
+        })?;
+        Ok(id)
+    }
+
+    // Rebuilds a directory from saved employees, keeping their IDs. `next_id` stops IDs of
+    // people who have since been removed from being handed out again.
+    pub fn restore(
+        employees: impl IntoIterator<Item = Employee>,
+        next_id: EmployeeId,
+    ) -> Result<Directory, DirectoryError> {
+        let mut directory = Directory::new();
+        for employee in employees {
+            directory.insert(employee)?;
+        }
+        directory.reserve_ids(next_id);
+        Ok(directory)
+    }
+
+    // Adds an employee with the ID they already have, as restoring and replaying do.
+    pub(super) fn insert(&mut self, employee: Employee) -> Result<(), DirectoryError> {
+        if self.employees.contains_key(&employee.id) {
+            return Err(DirectoryError::DuplicateId(employee.id));
+        }
+        self.check_free(&employee.name, &employee.department, None)?;
+        let next_id = employee
+            .id
+            .next()
+            .ok_or(DirectoryError::IdOverflow(employee.id))?;
+
+        self.next_id = self.next_id.max(next_id);
+        self.index.insert(&employee);
+        self.employees.insert(employee.id, employee);
+        Ok(())
+    }
+
+    // Fails if someone other than `except` already has `name` in `department`.
+    fn check_free(
+        &self,
+        name: &str,
+        department: &str,
+        except: Option<EmployeeId>,
+    ) -> Result<(), DirectoryError> {
+        let taken = self
+            .named(name)
+            .into_iter()
+            .find(|other| Some(other.id) != except && other.department == department);
+        match taken {
+            Some(other) => Err(DirectoryError::Duplicate {
+                name: other.name.clone(),
+                department: other.department.clone(),
+                id: other.id,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn employee(&self, id: EmployeeId) -> &Employee {
+        &self.employees[&id]
+    }
+
+    pub fn get(&self, id: EmployeeId) -> Option<&Employee> {
+        self.employees.get(&id)
+    }
+
+    // Everyone called `name`, ignoring case, in ID order.
+    pub fn named(&self, name: &str) -> Vec<&Employee> {
+        self.index
+            .named(&name_key(&normalize(name)))
+            .map(|id| self.employee(id))
+            .collect()
+    }
+
+    pub fn find(&self, employee: &EmployeeRef) -> Result<&Employee, DirectoryError> {
+        match employee {
+            EmployeeRef::Id(id) => self.get(*id).ok_or(DirectoryError::NoSuchId(*id)),
+            EmployeeRef::Name {
+                name,
+                department: Some(department),
+            } => {
+                let department = self.resolve_department(department)?;
+                self.named(name)
+                    .into_iter()
+                    .find(|employee| employee.department == department)
+                    .ok_or_else(|| DirectoryError::NoSuchEmployee {
+                        name: name.clone(),
+                        department,
+                    })
+            }
+            EmployeeRef::Name {
+                name,
+                department: None,
+            } => match self.named(name).as_slice() {
+                [] => Err(DirectoryError::NoSuchName(name.clone())),
+                [employee] => Ok(employee),
+                several => Err(DirectoryError::Ambiguous {
+                    name: name.clone(),
+                    ids: several.iter().map(|employee| employee.id).collect(),
+                }),
+            },
+        }
+    }
+
+    pub fn remove(&mut self, employee: &EmployeeRef) -> Result<Employee, DirectoryError> {
+        let id = self.find(employee)?.id;
+        let employee = self.employees.remove(&id).expect("found above");
+        self.index.remove(&employee);
+        self.drop_stale_managers();
+        Ok(employee)
+    }
+
+    // Nothing changes unless the employee exists and `to` has nobody else with their name.
+    // Returns the moved employee and the department they moved out of.
+    pub fn move_employee(
+        &mut self,
+        employee: &EmployeeRef,
+        to: &str,
+    ) -> Result<(&Employee, String), DirectoryError> {
+        let to = self.target_department(to)?;
+        let id = self.find(employee)?.id;
+        self.check_free(&self.employees[&id].name, &to, Some(id))?;
+
+        let employee = self.employees.get_mut(&id).expect("found above");
+        self.index.remove(employee);
+        let from = std::mem::replace(&mut employee.department, to);
+        self.index.insert(employee);
+        self.drop_stale_managers();
+        Ok((&self.employees[&id], from))
+    }
+
+    // Managers of departments that have emptied, and managers who have left, are forgotten.
+    fn drop_stale_managers(&mut self) {
+        let (index, employees) = (&self.index, &self.employees);
+        self.managers.retain(|department, manager| {
+            index.has_department(department) && employees.contains_key(manager)
+        });
+    }
+
+    // Finds an existing department from its full path or, if that is unique, from the end of
+    // its path: "Platform" finds "Engineering/Platform". A leading "/" only accepts full paths.
+    pub fn resolve_department(&self, department: &str) -> Result<String, DirectoryError> {
+        let path = normalize_department(department);
+        if path.is_empty() {
+            return Err(DirectoryError::NoSuchDepartment(department.to_string()));
+        }
+        if self.index.has_department(&path) {
+            return Ok(path);
+        }
+        if department.trim_start().starts_with('/') {
+            return Err(DirectoryError::NoSuchDepartment(path));
+        }
+
+        let suffix = format!("/{path}");
+        let mut matches: Vec<String> = self
+            .index
+            .paths()
+            .into_iter()
+            .filter(|candidate| candidate.ends_with(&suffix))
+            .map(str::to_string)
+            .collect();
+        match matches.len() {
+            0 => Err(DirectoryError::NoSuchDepartment(path)),
+            1 => Ok(matches.remove(0)),
+            _ => Err(DirectoryError::AmbiguousDepartment {
+                name: path,
+                paths: matches,
+            }),
+        }
+    }
+
+    // Where someone added or moved to `department` ends up: the existing department it
+    // names, or a new one at exactly that path.
+    fn target_department(&self, department: &str) -> Result<String, DirectoryError> {
+        match self.resolve_department(department) {
+            Err(DirectoryError::NoSuchDepartment(_))
+                if !normalize_department(department).is_empty() =>
+            {
+                Ok(normalize_department(department))
+            }
+            resolved => resolved,
+        }
+    }
+
+    // Returns the department's full path and its new manager.
+    pub fn set_manager(
+        &mut self,
+        department: &str,
+        manager: &EmployeeRef,
+    ) -> Result<(String, &Employee), DirectoryError> {
+        let department = self.resolve_department(department)?;
+        let id = self.find(manager)?.id;
+        self.managers.insert(department.clone(), id);
+        Ok((department, self.employee(id)))
+    }
+
+    // Returns the department's full path and who used to manage it, if anyone did.
+    pub fn unset_manager(
+        &mut self,
+        department: &str,
+    ) -> Result<(String, Option<EmployeeId>), DirectoryError> {
+        let department = self.resolve_department(department)?;
+        let manager = self.managers.remove(&department);
+        Ok((department, manager))
+    }
+
+    // Who manages exactly this department.
+    pub fn manager(&self, department: &str) -> Option<&Employee> {
+        let id = self.managers.get(department)?;
+        self.get(*id)
+    }
+
+    // Who the employee reports to: the manager of their department or, failing that, of the
+    // nearest department above it. Managers report to whoever manages the level above them.
+    pub fn manager_of(&self, id: EmployeeId) -> Option<&Employee> {
+        let employee = self.get(id)?;
+        std::iter::once(employee.department.as_str())
+            .chain(parent_departments(&employee.department))
+            .filter_map(|department| self.manager(department))
+            .find(|manager| manager.id != id)
+    }
+
+    // The departments this employee manages, in path order.
+    pub fn managed_by(&self, id: EmployeeId) -> Vec<&str> {
+        self.managers
+            .iter()
+            .filter(|(_, manager)| **manager == id)
+            .map(|(department, _)| department.as_str())
+            .collect()
+    }
+
+    // Every department with its manager, in path order.
+    pub fn managers(&self) -> impl Iterator<Item = (&str, EmployeeId)> {
+        self.managers
+            .iter()
+            .map(|(department, id)| (department.as_str(), *id))
+    }
+
+    // Every department path, including ones that only hold other departments, in path order.
+    pub fn department_paths(&self) -> Vec<&str> {
+        self.index.paths().into_iter().collect()
+    }
+
+    // The employees of one department, sorted alphabetically and then by ID.
+    pub fn department(&self, department: &str) -> Option<Vec<&Employee>> {
+        let members = self.index.department(department)?;
+        Some(members.map(|id| self.employee(id)).collect())
+    }
+
+    // Every department with its employees, both sorted alphabetically.
+    pub fn all(&self) -> Vec<(&str, Vec<&Employee>)> {
+        self.index
+            .departments()
+            .map(|(department, _)| {
+                let employees = self
+                    .department(department)
+                    .expect("department names come from the index");
+                (department, employees)
+            })
+            .collect()
+    }
+
+    // The employees in scope that match every condition; a department's scope includes the
+    // departments inside it. Without a sort they come grouped by department and ordered by
+    // name, straight from the index.
+    pub fn query(&self, query: &Query) -> Result<Vec<&Employee>, DirectoryError> {
+        let department = match &query.scope {
+            Scope::All => String::new(),
+            Scope::Department(department) => self.resolve_department(department)?,
+        };
+
+        let mut employees: Vec<&Employee> = self
+            .index
+            .subtree_members(&department)
+            .map(|id| self.employee(id))
+            .filter(|employee| query.matches(employee))
+            .collect();
+        if let Some(sort) = query.sort {
+            employees.sort_by(|a, b| sort.compare(a, b));
+        }
+        Ok(employees)
+    }
+
+    // How many people have each value of `field`, ordered by value. People without the
+    // field are counted under None.
+    pub fn count_by(&self, field: Field) -> Vec<(Option<String>, usize)> {
+        if field == Field::Department {
+            return self
+                .index
+                .departments()
+                .map(|(department, count)| (Some(department.to_string()), count))
+                .collect();
+        }
+
+        let mut counts: BTreeMap<Option<String>, usize> = BTreeMap::new();
+        for employee in self.employees.values() {
+            *counts.entry(field.text(employee)).or_default() += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    // Everyone whose name starts with `prefix`, ignoring case, ordered by name.
+    pub fn find_prefix(&self, prefix: &str) -> Vec<&Employee> {
+        let prefix = name_key(&normalize(prefix));
+        self.index
+            .prefixed(&prefix)
+            .map(|id| self.employee(id))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.employees.len()
+    }
+
+    // Every employee in ID order.
+    pub fn employees(&self) -> impl Iterator<Item = &Employee> {
+        self.employees.values()
+    }
+
+    // The ID the next employee added will get.
+    pub fn next_id(&self) -> EmployeeId {
+        self.next_id
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.employees.is_empty()
+    }
+
+    // Makes sure IDs below `next_id` are never handed out, even to a new employee after the
+    // people who had them were removed or their addition was undone.
+    pub(super) fn reserve_ids(&mut self, next_id: EmployeeId) {
+        self.next_id = self.next_id.max(next_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::employee_directory::query::{Condition, Op, Sort};
+
+    fn by_name(name: &str) -> EmployeeRef {
+        EmployeeRef::Name {
+            name: name.to_string(),
+            department: None,
+        }
+    }
+
+    fn names<'a>(employees: &[&'a Employee]) -> Vec<&'a str> {
+        employees
+            .iter()
+            .map(|employee| employee.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn add_and_list_sorted() {
+        let mut directory = Directory::new();
+        directory.add("Sally", "Engineering").unwrap();
+        directory.add("Amir", "Sales").unwrap();
+        directory.add("Bob", "Engineering").unwrap();
+
+        assert_eq!(
+            directory.department("Engineering").map(|e| names(&e)),
+            Some(vec!["Bob", "Sally"])
+        );
+        assert_eq!(directory.department("Marketing"), None);
+        let all: Vec<(&str, Vec<&str>)> = directory
+            .all()
+            .into_iter()
+            .map(|(department, employees)| (department, names(&employees)))
+            .collect();
+        assert_eq!(
+            all,
+            [
+                ("Engineering", vec!["Bob", "Sally"]),
+                ("Sales", vec!["Amir"])
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_and_move() {
+        let mut directory = Directory::new();
+        let sally = directory.add("Sally", "Engineering").unwrap();
+
+        assert_eq!(
+            directory.move_employee(&by_name("Amir"), "Sales"),
+            Err(DirectoryError::NoSuchName("Amir".to_string()))
+        );
+        let (moved, from) = directory.move_employee(&by_name("sally"), "Sales").unwrap();
+        assert_eq!((moved.id, moved.department.as_str()), (sally, "Sales"));
+        assert_eq!(from, "Engineering");
+        assert_eq!(directory.department("Engineering"), None);
+
+        let in_engineering = EmployeeRef::Name {
+            name: "Sally".to_string(),
+            department: Some("Engineering".to_string()),
+        };
+        assert_eq!(
+            directory.remove(&in_engineering),
+            Err(DirectoryError::NoSuchDepartment("Engineering".to_string()))
+        );
+        assert_eq!(
+            directory.remove(&EmployeeRef::Id(sally)).unwrap().name,
+            "Sally"
+        );
+        assert!(directory.is_empty());
+        assert_eq!(
+            directory.remove(&EmployeeRef::Id(sally)),
+            Err(DirectoryError::NoSuchId(sally))
+        );
+    }
+
+    #[test]
+    fn ids_are_stable_and_duplicates_refused() {
+        let mut directory = Directory::new();
+        let first = directory.add("Mary  Jane", "Human Resources").unwrap();
+        let second = directory.add("Mary Jane", "Engineering").unwrap();
+
+        assert_eq!((first, second), (EmployeeId(1), EmployeeId(2)));
+        assert_eq!(
+            directory.add("mary jane", "Human  Resources"),
+            Err(DirectoryError::Duplicate {
+                name: "Mary Jane".to_string(),
+                department: "Human Resources".to_string(),
+                id: first
+            })
+        );
+        assert_eq!(
+            directory.find(&by_name("Mary Jane")),
+            Err(DirectoryError::Ambiguous {
+                name: "Mary Jane".to_string(),
+                ids: vec![first, second]
+            })
+        );
+        assert!(matches!(
+            directory.move_employee(&EmployeeRef::Id(second), "Human Resources"),
+            Err(DirectoryError::Duplicate { .. })
+        ));
+
+        directory.remove(&EmployeeRef::Id(second)).unwrap();
+        assert_eq!(directory.add("Amir", "Sales"), Ok(EmployeeId(3)));
+        assert_eq!(directory.find(&by_name("Mary Jane")).unwrap().id, first);
+    }
+
+    #[test]
+    fn queries_filter_and_sort() {
+        let mut directory = Directory::new();
+        let manager = |date: &str| Details {
+            title: Some("Manager".to_string()),
+            email: None,
+            start_date: date.parse().ok(),
+        };
+        directory
+            .add_with("Sally", "Engineering", manager("2019-06-01"))
+            .unwrap();
+        directory.add("Bob", "Engineering").unwrap();
+        directory
+            .add_with("Ana", "Engineering", manager("2022-01-10"))
+            .unwrap();
+        directory
+            .add_with("Amir", "Sales", manager("2020-05-05"))
+            .unwrap();
+
+        let query: Query = Query {
+            conditions: vec![Condition {
+                field: Field::Title,
+                op: Op::Eq,
+                value: Field::Title.parse_value("manager").unwrap(),
+            }],
+            sort: Some(Sort {
+                field: Field::StartDate,
+                descending: true,
+            }),
+            ..Query::new(Scope::Department("Engineering".to_string()))
+        };
+        assert_eq!(names(&directory.query(&query).unwrap()), ["Ana", "Sally"]);
+
+        let everyone = Query::new(Scope::All);
+        assert_eq!(
+            names(&directory.query(&everyone).unwrap()),
+            ["Ana", "Bob", "Sally", "Amir"]
+        );
+        assert_eq!(
+            directory.query(&Query::new(Scope::Department("HR".to_string()))),
+            Err(DirectoryError::NoSuchDepartment("HR".to_string()))
+        );
+
+        assert_eq!(
+            directory.count_by(Field::Department),
+            [
+                (Some("Engineering".to_string()), 3),
+                (Some("Sales".to_string()), 1)
+            ]
+        );
+        assert_eq!(
+            directory.count_by(Field::Title),
+            [(None, 1), (Some("Manager".to_string()), 3)]
+        );
+    }
+
+    #[test]
+    fn finds_by_name_prefix() {
+        let mut directory = Directory::new();
+        directory.add("Sally", "Engineering").unwrap();
+        directory.add("sal", "Sales").unwrap();
+        directory.add("Salvador", "Sales").unwrap();
+        directory.add("Amir", "Sales").unwrap();
+
+        assert_eq!(
+            names(&directory.find_prefix("SAL")),
+            ["sal", "Sally", "Salvador"]
+        );
+        assert_eq!(names(&directory.find_prefix("sally")), ["Sally"]);
+        assert!(directory.find_prefix("Bob").is_empty());
+    }
+
+    #[test]
+    fn restore_keeps_ids() {
+        let mut directory = Directory::new();
+        directory.add("Sally", "Engineering").unwrap();
+        let amir = directory.add("Amir", "Sales").unwrap();
+        directory.remove(&EmployeeRef::Id(amir)).unwrap();
+
+        let restored =
+            Directory::restore(directory.employees().cloned(), directory.next_id()).unwrap();
+        assert_eq!(restored, directory);
+        assert_eq!(restored.next_id(), EmployeeId(3));
+
+        let twice = directory.employees().chain(directory.employees()).cloned();
+        assert_eq!(
+            Directory::restore(twice, EmployeeId(1)),
+            Err(DirectoryError::DuplicateId(EmployeeId(1)))
+        );
+
+        let last = Employee {
+            id: EmployeeId(u32::MAX),
+            ..directory.employees().next().unwrap().clone()
+        };
+        assert_eq!(
+            Directory::restore([last], EmployeeId(1)),
+            Err(DirectoryError::IdOverflow(EmployeeId(u32::MAX)))
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+// Handed out by the directory when someone is added and never reused, so it keeps pointing
+// at the same person when names clash or people move between departments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EmployeeId(pub u32);
+
+impl EmployeeId {
+    // The ID handed out after this one, or None once every u32 has been used.
+    pub fn next(self) -> Option<EmployeeId> {
+        self.0.checked_add(1).map(EmployeeId)
+    }
+}
+
+impl fmt::Display for EmployeeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl FromStr for EmployeeId {
+    type Err = ();
+
+    // "#12", as the directory prints it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('#').ok_or(())?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+        digits.parse().map(EmployeeId).map_err(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Employee {
+    pub id: EmployeeId,
+    pub name: String,
+    pub department: String,
+    #[serde(flatten)]
+    pub details: Details,
+}
+
+// How a command names an employee: by ID, or by name, optionally narrowed to a department
+// when several people share it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmployeeRef {
+    Id(EmployeeId),
+    Name {
+        name: String,
+        department: Option<String>,
+    },
+}
+
+// The optional parts of an employee record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Details {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<Date>,
+}
+
+impl fmt::Display for Employee {
+    // "Sally (#1), Staff Engineer, sally@example.com, since 2021-03-01"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)?;
+        if let Some(title) = &self.details.title {
+            write!(f, ", {title}")?;
+        }
+        if let Some(email) = &self.details.email {
+            write!(f, ", {email}")?;
+        }
+        if let Some(start_date) = &self.details.start_date {
+            write!(f, ", since {start_date}")?;
+        }
+        Ok(())
+    }
+}
+
+// A calendar date written as YYYY-MM-DD. Dates order chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDate(pub String);
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a date, expected YYYY-MM-DD", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDate {}
+
+impl Date {
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year.is_multiple_of(4)
+                && (!year.is_multiple_of(100) || year.is_multiple_of(400)) =>
+            {
+                29
+            }
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month)
+            .contains(&day)
+            .then_some(Date { year, month, day })
+    }
+
+    // The date `days` days after 1970-01-01, using the proleptic Gregorian calendar.
+    pub fn from_unix_days(days: u64) -> Date {
+        // Counted from 0000-03-01 so the leap day falls at the end of each year; see
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = era * 400 + year_of_era + u64::from(month <= 2);
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = InvalidDate;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidDate(s.to_string());
+        let number = |part: &str, width: usize| {
+            if part.len() == width && part.bytes().all(|b| b.is_ascii_digit()) {
+                part.parse::<u16>().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        };
+
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        Date::new(
+            number(year, 4)?,
+            number(month, 2)? as u8,
+            number(day, 2)? as u8,
+        )
+        .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = InvalidDate;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+// Trims the ends and collapses runs of whitespace inside, so "Mary  Jane" and "Mary Jane"
+// are the same person to the directory.
+pub fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Departments nest with slashes, as in "Engineering/Platform/Storage". Each part is
+// normalized and empty parts are dropped, so " Engineering / Platform/" is the same path.
+pub fn normalize_department(text: &str) -> String {
+    text.split('/')
+        .map(normalize)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// The departments `path` sits in, innermost first: "A/B/C" gives "A/B" and then "A".
+pub fn parent_departments(path: &str) -> impl Iterator<Item = &str> {
+    path.match_indices('/')
+        .rev()
+        .map(|(index, _)| &path[..index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_parse_and_order() {
+        let date: Date = "2024-02-29".parse().unwrap();
+
+        assert_eq!(Date::from_unix_days(0).to_string(), "1970-01-01");
+        assert_eq!(Date::from_unix_days(19_782), date);
+        assert_eq!(Date::from_unix_days(20_744).to_string(), "2026-10-18");
+
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert!(date < "2024-03-01".parse().unwrap());
+        for bad in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-1-01",
+            "24-01-01",
+            "soon",
+            "2024-01-01-01",
+        ] {
+            assert_eq!(bad.parse::<Date>(), Err(InvalidDate(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn ids_and_display() {
+        assert_eq!("#12".parse(), Ok(EmployeeId(12)));
+        assert_eq!("12".parse::<EmployeeId>(), Err(()));
+        assert_eq!("#".parse::<EmployeeId>(), Err(()));
+
+        let employee = Employee {
+            id: EmployeeId(3),
+            name: "Mary Jane".to_string(),
+            department: "Human Resources".to_string(),
+            details: Details {
+                title: Some("Recruiter".to_string()),
+                email: None,
+                start_date: "2021-03-01".parse().ok(),
+            },
+        };
+        assert_eq!(
+            employee.to_string(),
+            "Mary Jane (#3), Recruiter, since 2021-03-01"
+        );
+        assert_eq!(normalize("  Mary \t Jane "), "Mary Jane");
+        assert_eq!(
+            normalize_department(" Engineering /  Platform//Storage/"),
+            "Engineering/Platform/Storage"
+        );
+        assert_eq!(
+            parent_departments("A/B/C").collect::<Vec<_>>(),
+            ["A/B", "A"]
+        );
+    }
+}
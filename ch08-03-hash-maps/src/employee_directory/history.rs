@@ -0,0 +1,800 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    directory::{Directory, DirectoryError},
+    employee::{Date, Details, Employee, EmployeeId, EmployeeRef},
+    storage::{LoadStatus, Record, StorageError, backup_path},
+};
+
+// Seconds since 1970-01-01 00:00:00 UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    pub fn now() -> Timestamp {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Timestamp(since_epoch.as_secs())
+    }
+}
+
+impl fmt::Display for Timestamp {
+    // "2025-10-09 08:53:20", in UTC.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (days, seconds) = (self.0 / 86_400, self.0 % 86_400);
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}",
+            Date::from_unix_days(days),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+// One change to the directory, with enough in it to apply it again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Added(Employee),
+    Removed(Employee),
+    Moved {
+        id: EmployeeId,
+        name: String,
+        from: String,
+        to: String,
+    },
+    ManagerSet {
+        department: String,
+        id: EmployeeId,
+        name: String,
+    },
+    ManagerUnset {
+        department: String,
+    },
+}
+
+impl Event {
+    // Departments are recorded as full paths, so they are applied with a leading "/" and
+    // cannot be mistaken for a department that was created later.
+    fn apply(&self, directory: &mut Directory) -> Result<(), DirectoryError> {
+        match self {
+            Event::Added(employee) => directory.insert(employee.clone()),
+            Event::Removed(employee) => directory.remove(&EmployeeRef::Id(employee.id)).map(|_| ()),
+            Event::Moved { id, to, .. } => directory
+                .move_employee(&EmployeeRef::Id(*id), &format!("/{to}"))
+                .map(|_| ()),
+            Event::ManagerSet { department, id, .. } => directory
+                .set_manager(&format!("/{department}"), &EmployeeRef::Id(*id))
+                .map(|_| ()),
+            Event::ManagerUnset { department } => directory
+                .unset_manager(&format!("/{department}"))
+                .map(|_| ()),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Added(employee) => write!(
+                f,
+                "Added {} ({}) to {}",
+                employee.name, employee.id, employee.department
+            ),
+            Event::Removed(employee) => write!(
+                f,
+                "Removed {} ({}) from {}",
+                employee.name, employee.id, employee.department
+            ),
+            Event::Moved { id, name, from, to } => {
+                write!(f, "Moved {name} ({id}) from {from} to {to}")
+            }
+            Event::ManagerSet {
+                department,
+                id,
+                name,
+            } => write!(f, "Set the manager of {department} to {name} ({id})"),
+            Event::ManagerUnset { department } => write!(f, "Unset the manager of {department}"),
+        }
+    }
+}
+
+// One line of the log. Undo and Redo are recorded rather than erasing anything, so the log
+// only ever grows and still shows what was undone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "entry", rename_all = "snake_case")]
+pub enum Entry {
+    // The directory as it was when the log was started, which cannot be undone.
+    Start {
+        at: Timestamp,
+        next_id: EmployeeId,
+        events: Vec<Event>,
+    },
+    // What one command did: a single event, or one per employee for an Import.
+    Change {
+        at: Timestamp,
+        events: Vec<Event>,
+    },
+    Undo {
+        at: Timestamp,
+    },
+    Redo {
+        at: Timestamp,
+    },
+}
+
+impl Entry {
+    pub fn at(&self) -> Timestamp {
+        match self {
+            Entry::Start { at, .. }
+            | Entry::Change { at, .. }
+            | Entry::Undo { at }
+            | Entry::Redo { at } => *at,
+        }
+    }
+
+    pub fn events(&self) -> &[Event] {
+        match self {
+            Entry::Start { events, .. } | Entry::Change { events, .. } => events,
+            Entry::Undo { .. } | Entry::Redo { .. } => &[],
+        }
+    }
+}
+
+// Where the log stands after some of its entries, as indexes into it: the Start entry, the
+// changes in effect, oldest first, and the changes undone, most recently undone last.
+#[derive(Debug, Clone, Default)]
+struct Stacks {
+    start: Option<usize>,
+    done: Vec<usize>,
+    undone: Vec<usize>,
+}
+
+impl Stacks {
+    // Returns the change an Undo or Redo applies to. Undo and Redo with nothing to work on
+    // are never logged, but are ignored rather than refused if they are found.
+    fn push(&mut self, index: usize, entry: &Entry) -> Option<usize> {
+        match entry {
+            Entry::Start { .. } => {
+                *self = Stacks {
+                    start: Some(index),
+                    ..Stacks::default()
+                };
+                None
+            }
+            Entry::Change { .. } => {
+                self.done.push(index);
+                self.undone.clear();
+                None
+            }
+            Entry::Undo { .. } => {
+                let change = self.done.pop()?;
+                self.undone.push(change);
+                Some(change)
+            }
+            Entry::Redo { .. } => {
+                let change = self.undone.pop()?;
+                self.done.push(change);
+                Some(change)
+            }
+        }
+    }
+
+    fn in_effect(&self) -> impl Iterator<Item = usize> + '_ {
+        self.start.iter().chain(&self.done).copied()
+    }
+}
+
+// What the History command shows for one moment in the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Moment<'a> {
+    Started { employees: usize },
+    Done(&'a Event),
+    Undone(&'a Event),
+    Redone(&'a Event),
+}
+
+impl fmt::Display for Moment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Moment::Started { employees: 1 } => write!(f, "Started with 1 employee"),
+            Moment::Started { employees } => write!(f, "Started with {employees} employees"),
+            Moment::Done(event) => write!(f, "{event}"),
+            Moment::Undone(event) => write!(f, "Undid: {event}"),
+            Moment::Redone(event) => write!(f, "Redid: {event}"),
+        }
+    }
+}
+
+// The directory together with the append-only log of every change made to it.
+//
+// Changes go through the history, which applies them to the directory and logs the events
+// they caused. The directory can always be rebuilt by replaying the log from its Start
+// entry: Undo and Redo do exactly that, leaving out the changes that are undone.
+#[derive(Debug, Clone)]
+pub struct History {
+    directory: Directory,
+    entries: Vec<Entry>,
+    stacks: Stacks,
+    // How many entries are already in the log file.
+    saved: usize,
+    clock: fn() -> Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    // Every log begins with the directory it was started from; without that, the changes
+    // after it have nothing to apply to.
+    NoStart,
+    Directory(DirectoryError),
+}
+
+impl From<DirectoryError> for ReplayError {
+    fn from(error: DirectoryError) -> Self {
+        ReplayError::Directory(error)
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::NoStart => write!(f, "the log does not start with a directory"),
+            ReplayError::Directory(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// The log kept next to a saved directory: "employee-directory.json" has
+// "employee-directory.log".
+pub fn log_path(directory_file: &Path) -> PathBuf {
+    directory_file.with_extension("log")
+}
+
+impl History {
+    // Starts a log from `directory` as it is now.
+    pub fn new(directory: Directory) -> History {
+        History::with_clock(directory, Timestamp::now)
+    }
+
+    // As `new`, with the time of each change taken from `clock`.
+    pub fn with_clock(directory: Directory, clock: fn() -> Timestamp) -> History {
+        let mut events: Vec<Event> = directory.employees().cloned().map(Event::Added).collect();
+        events.extend(
+            directory
+                .managers()
+                .map(|(department, id)| Event::ManagerSet {
+                    department: department.to_string(),
+                    id,
+                    name: directory.get(id).expect("managers work here").name.clone(),
+                }),
+        );
+        let start = Entry::Start {
+            at: clock(),
+            next_id: directory.next_id(),
+            events,
+        };
+        History {
+            directory,
+            entries: vec![start],
+            stacks: Stacks {
+                start: Some(0),
+                ..Stacks::default()
+            },
+            saved: 0,
+            clock,
+        }
+    }
+
+    // Rebuilds the directory from a log read back from disk.
+    pub fn replay(entries: Vec<Entry>) -> Result<History, ReplayError> {
+        if !matches!(entries.first(), Some(Entry::Start { .. })) {
+            return Err(ReplayError::NoStart);
+        }
+        let mut stacks = Stacks::default();
+        for (index, entry) in entries.iter().enumerate() {
+            stacks.push(index, entry);
+        }
+        let directory = rebuild(&entries, &stacks)?;
+        Ok(History {
+            directory,
+            saved: entries.len(),
+            entries,
+            stacks,
+            clock: Timestamp::now,
+        })
+    }
+
+    pub fn directory(&self) -> &Directory {
+        &self.directory
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    // Logs a change that has already been applied to the directory. Nothing is logged for
+    // a command that did not change anything.
+    fn record(&mut self, events: Vec<Event>) {
+        if events.is_empty() {
+            return;
+        }
+        let change = Entry::Change {
+            at: (self.clock)(),
+            events,
+        };
+        self.stacks.push(self.entries.len(), &change);
+        self.entries.push(change);
+    }
+
+    pub fn add_with(
+        &mut self,
+        name: &str,
+        department: &str,
+        details: Details,
+    ) -> Result<EmployeeId, DirectoryError> {
+        let id = self.directory.add_with(name, department, details)?;
+        let employee = self.directory.get(id).expect("just added").clone();
+        self.record(vec![Event::Added(employee)]);
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, employee: &EmployeeRef) -> Result<Employee, DirectoryError> {
+        let employee = self.directory.remove(employee)?;
+        self.record(vec![Event::Removed(employee.clone())]);
+        Ok(employee)
+    }
+
+    pub fn move_employee(
+        &mut self,
+        employee: &EmployeeRef,
+        to: &str,
+    ) -> Result<(&Employee, String), DirectoryError> {
+        let (employee, from) = self.directory.move_employee(employee, to)?;
+        let event = Event::Moved {
+            id: employee.id,
+            name: employee.name.clone(),
+            from: from.clone(),
+            to: employee.department.clone(),
+        };
+        let id = employee.id;
+        self.record(vec![event]);
+        Ok((self.directory.get(id).expect("just moved"), from))
+    }
+
+    pub fn set_manager(
+        &mut self,
+        department: &str,
+        manager: &EmployeeRef,
+    ) -> Result<(String, &Employee), DirectoryError> {
+        let (department, manager) = self.directory.set_manager(department, manager)?;
+        let event = Event::ManagerSet {
+            department: department.clone(),
+            id: manager.id,
+            name: manager.name.clone(),
+        };
+        let id = manager.id;
+        self.record(vec![event]);
+        Ok((department, self.directory.get(id).expect("just set")))
+    }
+
+    pub fn unset_manager(
+        &mut self,
+        department: &str,
+    ) -> Result<(String, Option<EmployeeId>), DirectoryError> {
+        let (department, manager) = self.directory.unset_manager(department)?;
+        if manager.is_some() {
+            self.record(vec![Event::ManagerUnset {
+                department: department.clone(),
+            }]);
+        }
+        Ok((department, manager))
+    }
+
+    // As `Directory::import`, logged as one change so a single Undo takes it all back.
+    pub fn import(&mut self, records: &[Record]) -> Vec<DirectoryError> {
+        let mut events = Vec::new();
+        let mut skipped = Vec::new();
+        for record in records {
+            match self
+                .directory
+                .add_with(&record.name, &record.department, record.details())
+            {
+                Ok(id) => events.push(Event::Added(
+                    self.directory.get(id).expect("just added").clone(),
+                )),
+                Err(error) => skipped.push(error),
+            }
+        }
+        self.record(events);
+        skipped
+    }
+
+    // Takes back the latest change still in effect and returns its events, or None if
+    // there is nothing to undo.
+    pub fn undo(&mut self) -> Result<Option<&[Event]>, DirectoryError> {
+        if self.stacks.done.is_empty() {
+            return Ok(None);
+        }
+        self.step(Entry::Undo { at: (self.clock)() }).map(Some)
+    }
+
+    // Puts back the change most recently undone, unless something has changed since.
+    pub fn redo(&mut self) -> Result<Option<&[Event]>, DirectoryError> {
+        if self.stacks.undone.is_empty() {
+            return Ok(None);
+        }
+        self.step(Entry::Redo { at: (self.clock)() }).map(Some)
+    }
+
+    // Logs an Undo or Redo once the directory has been rebuilt without or with the change.
+    fn step(&mut self, entry: Entry) -> Result<&[Event], DirectoryError> {
+        let mut stacks = self.stacks.clone();
+        let change = stacks
+            .push(self.entries.len(), &entry)
+            .expect("checked by the caller");
+        self.directory = rebuild(&self.entries, &stacks)?;
+        self.stacks = stacks;
+        self.entries.push(entry);
+        Ok(self.entries[change].events())
+    }
+
+    // Everything in the log, oldest first, with the changes each Undo and Redo applied to.
+    // Undoing a change lists its events in the order they were taken back.
+    pub fn timeline(&self) -> Vec<(Timestamp, Moment<'_>)> {
+        let mut stacks = Stacks::default();
+        let mut moments = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let change = stacks
+                .push(index, entry)
+                .map(|change| &self.entries[change]);
+            let at = entry.at();
+            match (entry, change) {
+                (Entry::Start { events, .. }, _) => {
+                    let employees = events
+                        .iter()
+                        .filter(|event| matches!(event, Event::Added(_)))
+                        .count();
+                    if employees > 0 {
+                        moments.push((at, Moment::Started { employees }));
+                    }
+                }
+                (Entry::Change { events, .. }, _) => {
+                    moments.extend(events.iter().map(|event| (at, Moment::Done(event))));
+                }
+                (Entry::Undo { .. }, Some(change)) => moments.extend(
+                    change
+                        .events()
+                        .iter()
+                        .rev()
+                        .map(|event| (at, Moment::Undone(event))),
+                ),
+                (Entry::Redo { .. }, Some(change)) => moments.extend(
+                    change
+                        .events()
+                        .iter()
+                        .map(|event| (at, Moment::Redone(event))),
+                ),
+                (Entry::Undo { .. } | Entry::Redo { .. }, None) => {}
+            }
+        }
+        moments
+    }
+
+    // A log that cannot be read or replayed is moved aside to a ".corrupt" file, like a
+    // corrupt directory, and an empty history is returned. A last line without its newline
+    // is an append that was cut short: it is dropped, and cut off the file so the next
+    // append starts on a line of its own.
+    //
+    // A log with no complete entries counts as Missing, since the crash came before even the
+    // start was written; the saved directory is then the only record, and the caller should
+    // load that instead.
+    pub fn load(path: &Path) -> Result<(History, LoadStatus), StorageError> {
+        let log = match fs::read_to_string(path) {
+            Ok(log) => log,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok((History::new(Directory::new()), LoadStatus::Missing));
+            }
+            Err(error) => return Err(StorageError::Io(error)),
+        };
+
+        let complete = log.rfind('\n').map_or(0, |end| end + 1);
+        let entries = read_log(&log[..complete]);
+        if entries.as_ref().is_some_and(Vec::is_empty) {
+            truncate(path, 0)?;
+            return Ok((History::new(Directory::new()), LoadStatus::Missing));
+        }
+        match entries.and_then(|entries| History::replay(entries).ok()) {
+            Some(history) => {
+                if complete < log.len() {
+                    truncate(path, complete)?;
+                }
+                Ok((history, LoadStatus::Loaded))
+            }
+            None => {
+                let backup = backup_path(path);
+                fs::rename(path, &backup)?;
+                Ok((
+                    History::new(Directory::new()),
+                    LoadStatus::Recovered { backup },
+                ))
+            }
+        }
+    }
+
+    // Appends the entries logged since the last save, one JSON object per line. Entries
+    // already in the file are never rewritten.
+    pub fn save(&mut self, path: &Path) -> Result<(), StorageError> {
+        if self.saved == self.entries.len() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for entry in &self.entries[self.saved..] {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_data()?;
+        self.saved = self.entries.len();
+        Ok(())
+    }
+}
+
+// The directory with the changes in `stacks` applied, in order. IDs handed out by changes
+// that are no longer in effect stay reserved, so an undone employee's ID is never reused.
+fn rebuild(entries: &[Entry], stacks: &Stacks) -> Result<Directory, DirectoryError> {
+    let mut directory = Directory::new();
+    for index in stacks.in_effect() {
+        for event in entries[index].events() {
+            event.apply(&mut directory)?;
+        }
+    }
+
+    for entry in entries {
+        if let Entry::Start { next_id, .. } = entry {
+            directory.reserve_ids(*next_id);
+        }
+        for event in entry.events() {
+            if let Event::Added(employee) = event {
+                let next_id = employee
+                    .id
+                    .next()
+                    .ok_or(DirectoryError::IdOverflow(employee.id))?;
+                directory.reserve_ids(next_id);
+            }
+        }
+    }
+    Ok(directory)
+}
+
+// One entry per line; None if any line is not an entry.
+fn read_log(log: &str) -> Option<Vec<Entry>> {
+    log.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+// Cuts the log at `len` bytes, dropping an append that was cut short.
+fn truncate(path: &Path, len: usize) -> io::Result<()> {
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .set_len(len as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock() -> Timestamp {
+        Timestamp(1_760_000_000)
+    }
+
+    fn by_name(name: &str) -> EmployeeRef {
+        EmployeeRef::Name {
+            name: name.to_string(),
+            department: None,
+        }
+    }
+
+    #[test]
+    fn timestamps_show_in_utc() {
+        assert_eq!(clock().to_string(), "2025-10-09 08:53:20");
+        assert_eq!(Timestamp(0).to_string(), "1970-01-01 00:00:00");
+    }
+
+    #[test]
+    fn undo_and_redo_replay_the_log() {
+        let mut history = History::with_clock(Directory::new(), clock);
+        history
+            .add_with("Sally", "Engineering", Details::default())
+            .unwrap();
+        history
+            .add_with("Amir", "Sales", Details::default())
+            .unwrap();
+        history
+            .move_employee(&by_name("Amir"), "Engineering")
+            .unwrap();
+        let before_move = {
+            let mut directory = Directory::new();
+            directory.add("Sally", "Engineering").unwrap();
+            directory.add("Amir", "Sales").unwrap();
+            directory
+        };
+
+        let undone = history.undo().unwrap().unwrap().to_vec();
+        assert!(matches!(undone.as_slice(), [Event::Moved { .. }]));
+        assert_eq!(history.directory(), &before_move);
+
+        history.undo().unwrap();
+        assert_eq!(history.directory().len(), 1);
+        // Amir's ID stays taken even though adding him was undone.
+        assert_eq!(history.directory().next_id(), EmployeeId(3));
+
+        history.redo().unwrap();
+        assert_eq!(history.directory(), &before_move);
+
+        // A new change drops what was left to redo.
+        history.remove(&by_name("Sally")).unwrap();
+        assert_eq!(history.redo(), Ok(None));
+        assert_eq!(history.undo().unwrap().map(<[Event]>::len), Some(1));
+        assert_eq!(history.undo().unwrap().map(<[Event]>::len), Some(1));
+        assert_eq!(history.undo().unwrap().map(<[Event]>::len), Some(1));
+        assert_eq!(history.undo(), Ok(None));
+        assert!(history.directory().is_empty());
+
+        let replayed = History::replay(history.entries().to_vec()).unwrap();
+        assert_eq!(replayed.directory(), history.directory());
+    }
+
+    #[test]
+    fn starts_from_an_existing_directory() {
+        let mut directory = Directory::new();
+        directory.add("Sally", "Engineering/Platform").unwrap();
+        directory.add("Amir", "Engineering").unwrap();
+        directory.remove(&by_name("Amir")).unwrap();
+        directory.add("Bob", "Engineering").unwrap();
+        directory
+            .set_manager("Platform", &EmployeeRef::Id(EmployeeId(3)))
+            .unwrap();
+
+        let mut history = History::with_clock(directory.clone(), clock);
+        assert_eq!(history.undo(), Ok(None));
+
+        let replayed = History::replay(history.entries().to_vec()).unwrap();
+        assert_eq!(replayed.directory(), &directory);
+        assert_eq!(
+            replayed.timeline(),
+            [(clock(), Moment::Started { employees: 2 })]
+        );
+    }
+
+    #[test]
+    fn timeline_shows_who_changed_when() {
+        let mut history = History::with_clock(Directory::new(), clock);
+        history
+            .add_with("Sally", "Engineering", Details::default())
+            .unwrap();
+        history.import(&[
+            Record {
+                id: None,
+                name: "Amir".to_string(),
+                department: "Sales".to_string(),
+                title: None,
+                email: None,
+                start_date: None,
+            },
+            Record {
+                id: None,
+                name: "Sally".to_string(),
+                department: "Engineering".to_string(),
+                title: None,
+                email: None,
+                start_date: None,
+            },
+        ]);
+        history.set_manager("Sales", &by_name("Amir")).unwrap();
+        history.undo().unwrap();
+        history.undo().unwrap();
+        history.redo().unwrap();
+
+        let lines: Vec<String> = history
+            .timeline()
+            .iter()
+            .map(|(at, moment)| format!("{at}  {moment}"))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "2025-10-09 08:53:20  Added Sally (#1) to Engineering",
+                "2025-10-09 08:53:20  Added Amir (#2) to Sales",
+                "2025-10-09 08:53:20  Set the manager of Sales to Amir (#2)",
+                "2025-10-09 08:53:20  Undid: Set the manager of Sales to Amir (#2)",
+                "2025-10-09 08:53:20  Undid: Added Amir (#2) to Sales",
+                "2025-10-09 08:53:20  Redid: Added Amir (#2) to Sales",
+            ]
+        );
+    }
+
+    #[test]
+    fn save_appends_and_load_replays() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("directory.log");
+        let mut history = History::with_clock(Directory::new(), clock);
+        history
+            .add_with("Sally", "Engineering", Details::default())
+            .unwrap();
+        history.save(&path).unwrap();
+        let first_save = fs::read_to_string(&path).unwrap();
+
+        history
+            .add_with("Amir", "Sales", Details::default())
+            .unwrap();
+        history.undo().unwrap();
+        history.save(&path).unwrap();
+        let log = fs::read_to_string(&path).unwrap();
+        assert!(log.starts_with(&first_save));
+        assert_eq!(log.lines().count(), 4);
+
+        // A write cut short by a crash loses only the entry it was writing.
+        fs::write(&path, format!("{log}{{\"entry\":\"undo\",")).unwrap();
+        let (loaded, status) = History::load(&path).unwrap();
+        assert_eq!(status, LoadStatus::Loaded);
+        assert_eq!(loaded.directory(), history.directory());
+        assert_eq!(loaded.entries(), history.entries());
+        assert_eq!(fs::read_to_string(&path).unwrap(), log);
+
+        fs::write(&path, "{\"entry\":\"change\"}\n").unwrap();
+        let (loaded, status) = History::load(&path).unwrap();
+        assert!(matches!(status, LoadStatus::Recovered { .. }));
+        assert!(loaded.directory().is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn load_needs_a_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("directory.log");
+        let mut history = History::with_clock(Directory::new(), clock);
+        history
+            .add_with("Sally", "Engineering", Details::default())
+            .unwrap();
+        history.save(&path).unwrap();
+        let log = fs::read_to_string(&path).unwrap();
+        let (start, change) = log.split_once('\n').unwrap();
+
+        // A crash during the very first append leaves nothing to replay, so the saved
+        // directory is loaded instead.
+        fs::write(&path, &start[..start.len() / 2]).unwrap();
+        let (loaded, status) = History::load(&path).unwrap();
+        assert_eq!(status, LoadStatus::Missing);
+        assert!(loaded.directory().is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        fs::write(&path, change).unwrap();
+        assert_eq!(
+            History::replay(read_log(change).unwrap()).unwrap_err(),
+            ReplayError::NoStart
+        );
+        let (_, status) = History::load(&path).unwrap();
+        assert!(matches!(status, LoadStatus::Recovered { .. }));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
+
+use super::employee::{Employee, EmployeeId, parent_departments};
+
+// Lookup tables kept in step with the directory's employees, so listing a department or
+// searching by name walks an ordered set instead of collecting and sorting on every request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Index {
+    // Department path to its direct members, ordered by name and then ID. Departments that
+    // only hold other departments have no entry of their own.
+    departments: BTreeMap<String, BTreeSet<(String, EmployeeId)>>,
+    // Lower-cased names, for case-insensitive exact and prefix searches.
+    names: BTreeSet<(String, EmployeeId)>,
+}
+
+pub(super) fn name_key(name: &str) -> String {
+    name.to_lowercase()
+}
+
+impl Index {
+    pub(super) fn insert(&mut self, employee: &Employee) {
+        self.departments
+            .entry(employee.department.clone())
+            .or_default()
+            .insert((employee.name.clone(), employee.id));
+        self.names.insert((name_key(&employee.name), employee.id));
+    }
+
+    // Departments left without members are dropped.
+    pub(super) fn remove(&mut self, employee: &Employee) {
+        if let Some(members) = self.departments.get_mut(&employee.department) {
+            members.remove(&(employee.name.clone(), employee.id));
+            if members.is_empty() {
+                self.departments.remove(&employee.department);
+            }
+        }
+        self.names.remove(&(name_key(&employee.name), employee.id));
+    }
+
+    // Whether anyone works in `department` or in a department inside it.
+    pub(super) fn has_department(&self, department: &str) -> bool {
+        self.subtree(department).next().is_some()
+    }
+
+    // `department` and every department inside it that has members, in path order.
+    // The empty path is the top of the tree and holds every department.
+    fn subtree<'a>(
+        &'a self,
+        department: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a BTreeSet<(String, EmployeeId)>)> + 'a {
+        self.departments
+            .range::<str, _>((Bound::Included(department), Bound::Unbounded))
+            .take_while(move |(path, _)| path.starts_with(department))
+            .filter(move |(path, _)| {
+                department.is_empty()
+                    || path.len() == department.len()
+                    || path[department.len()..].starts_with('/')
+            })
+    }
+
+    // Everyone in `department` or below it, grouped by department and ordered by name.
+    pub(super) fn subtree_members<'a>(
+        &'a self,
+        department: &'a str,
+    ) -> impl Iterator<Item = EmployeeId> + 'a {
+        self.subtree(department)
+            .flat_map(|(_, members)| members.iter().map(|(_, id)| *id))
+    }
+
+    // Every department path, including ones that only hold other departments.
+    pub(super) fn paths(&self) -> BTreeSet<&str> {
+        let mut paths = BTreeSet::new();
+        for path in self.departments.keys() {
+            paths.insert(path.as_str());
+            paths.extend(parent_departments(path));
+        }
+        paths
+    }
+
+    // The members of `department` ordered by name and then ID.
+    pub(super) fn department(
+        &self,
+        department: &str,
+    ) -> Option<impl Iterator<Item = EmployeeId> + '_> {
+        let members = self.departments.get(department)?;
+        Some(members.iter().map(|(_, id)| *id))
+    }
+
+    // Every department in alphabetical order, with how many people are in it.
+    pub(super) fn departments(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.departments
+            .iter()
+            .map(|(department, members)| (department.as_str(), members.len()))
+    }
+
+    // Everyone whose lower-cased name is exactly `key`, in ID order.
+    pub(super) fn named(&self, key: &str) -> impl Iterator<Item = EmployeeId> + '_ {
+        let start = (key.to_string(), EmployeeId(u32::MIN));
+        let end = (key.to_string(), EmployeeId(u32::MAX));
+        self.names.range(start..=end).map(|(_, id)| *id)
+    }
+
+    // Everyone whose lower-cased name starts with `prefix`, ordered by name and then ID.
+    pub(super) fn prefixed<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = EmployeeId> + 'a {
+        self.names
+            .range((prefix.to_string(), EmployeeId(u32::MIN))..)
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .map(|(_, id)| *id)
+    }
+}
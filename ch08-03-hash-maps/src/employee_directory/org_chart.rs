@@ -0,0 +1,284 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use super::{directory::Directory, employee::Employee};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartFormat {
+    // An indented tree for the terminal.
+    Text,
+    // A Graphviz digraph, to be drawn with `dot -Tsvg`.
+    Dot,
+}
+
+pub fn render(directory: &Directory, root: Option<&str>, format: ChartFormat) -> String {
+    let tree = Tree::new(directory, root);
+    match format {
+        ChartFormat::Text => tree.text(),
+        ChartFormat::Dot => tree.dot(),
+    }
+}
+
+// The part of the department tree being drawn: everything, or one department and what is
+// inside it.
+struct Tree<'a> {
+    directory: &'a Directory,
+    paths: Vec<&'a str>,
+    tops: Vec<&'a str>,
+}
+
+fn parent(path: &str) -> Option<&str> {
+    path.rsplit_once('/').map(|(parent, _)| parent)
+}
+
+fn last_part(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, last)| last)
+}
+
+impl<'a> Tree<'a> {
+    fn new(directory: &'a Directory, root: Option<&'a str>) -> Tree<'a> {
+        let paths: Vec<&str> = directory
+            .department_paths()
+            .into_iter()
+            .filter(|path| match root {
+                Some(root) => {
+                    *path == root
+                        || path
+                            .strip_prefix(root)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                }
+                None => true,
+            })
+            .collect();
+        let tops = match root {
+            Some(root) => vec![root],
+            None => paths
+                .iter()
+                .copied()
+                .filter(|path| parent(path).is_none())
+                .collect(),
+        };
+        Tree {
+            directory,
+            paths,
+            tops,
+        }
+    }
+
+    fn children(&self, department: &str) -> impl Iterator<Item = &'a str> + '_ {
+        let department = department.to_string();
+        self.paths
+            .iter()
+            .copied()
+            .filter(move |path| parent(path) == Some(department.as_str()))
+    }
+
+    fn members(&self, department: &str) -> Vec<&'a Employee> {
+        self.directory.department(department).unwrap_or_default()
+    }
+
+    // Engineering/ [manager: Amir (#4)]
+    //     Sally (#1), Staff Engineer
+    //     Platform/
+    //         Bob (#2)
+    fn text(&self) -> String {
+        let mut chart = String::new();
+        for top in &self.tops {
+            self.write_text(&mut chart, top, top, 0);
+        }
+        chart
+    }
+
+    fn write_text(&self, chart: &mut String, department: &str, label: &str, depth: usize) {
+        let indent = "    ".repeat(depth);
+        write!(chart, "{indent}{label}/").unwrap();
+        if let Some(manager) = self.directory.manager(department) {
+            write!(chart, " [manager: {} ({})]", manager.name, manager.id).unwrap();
+        }
+        chart.push('\n');
+
+        for employee in self.members(department) {
+            writeln!(chart, "{indent}    {employee}").unwrap();
+        }
+        for child in self.children(department) {
+            self.write_text(chart, child, last_part(child), depth + 1);
+        }
+    }
+
+    // Departments are folders holding their sub-departments and members; a dashed edge runs
+    // from each manager to the department they manage.
+    fn dot(&self) -> String {
+        let mut chart = String::from("digraph org {\n    node [shape=box];\n");
+        let mut drawn = BTreeSet::new();
+
+        for department in &self.paths {
+            let label = if self.tops.contains(department) {
+                department
+            } else {
+                last_part(department)
+            };
+            writeln!(
+                chart,
+                "    {} [shape=folder, label={}];",
+                department_node(department),
+                quote(label)
+            )
+            .unwrap();
+            if let Some(parent) = parent(department).filter(|_| !self.tops.contains(department)) {
+                writeln!(
+                    chart,
+                    "    {} -> {};",
+                    department_node(parent),
+                    department_node(department)
+                )
+                .unwrap();
+            }
+            for employee in self.members(department) {
+                write_employee_node(&mut chart, employee);
+                drawn.insert(employee.id);
+                writeln!(
+                    chart,
+                    "    {} -> {};",
+                    department_node(department),
+                    employee_node(employee)
+                )
+                .unwrap();
+            }
+        }
+
+        for department in &self.paths {
+            if let Some(manager) = self.directory.manager(department) {
+                if drawn.insert(manager.id) {
+                    write_employee_node(&mut chart, manager);
+                }
+                writeln!(
+                    chart,
+                    "    {} -> {} [style=dashed, label=\"manages\"];",
+                    employee_node(manager),
+                    department_node(department)
+                )
+                .unwrap();
+            }
+        }
+
+        chart.push_str("}\n");
+        chart
+    }
+}
+
+fn department_node(department: &str) -> String {
+    quote(&format!("department:{department}"))
+}
+
+fn employee_node(employee: &Employee) -> String {
+    quote(&format!("employee:{}", employee.id.0))
+}
+
+fn write_employee_node(chart: &mut String, employee: &Employee) {
+    let mut label = format!("{}\n{}", employee.name, employee.id);
+    if let Some(title) = &employee.details.title {
+        label.push('\n');
+        label.push_str(title);
+    }
+    writeln!(
+        chart,
+        "    {} [label={}];",
+        employee_node(employee),
+        quote(&label)
+    )
+    .unwrap();
+}
+
+// A DOT string literal.
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::employee_directory::employee::{Details, EmployeeId, EmployeeRef};
+
+    fn company() -> Directory {
+        let mut directory = Directory::new();
+        let staff = Details {
+            title: Some("Staff Engineer".to_string()),
+            ..Details::default()
+        };
+        directory
+            .add_with("Sally", "Engineering/Platform", staff)
+            .unwrap();
+        directory
+            .add("Bob", "Engineering/Platform/Storage")
+            .unwrap();
+        directory.add("Amir", "Engineering").unwrap();
+        directory.add("Dee \"D\" Jones", "Sales").unwrap();
+        directory
+            .set_manager("Platform", &EmployeeRef::Id(EmployeeId(3)))
+            .unwrap();
+        directory
+    }
+
+    #[test]
+    fn text_chart() {
+        let directory = company();
+
+        assert_eq!(
+            render(&directory, None, ChartFormat::Text),
+            "\
+Engineering/
+    Amir (#3)
+    Platform/ [manager: Amir (#3)]
+        Sally (#1), Staff Engineer
+        Storage/
+            Bob (#2)
+Sales/
+    Dee \"D\" Jones (#4)
+"
+        );
+        assert_eq!(
+            render(&directory, Some("Engineering/Platform"), ChartFormat::Text),
+            "\
+Engineering/Platform/ [manager: Amir (#3)]
+    Sally (#1), Staff Engineer
+    Storage/
+        Bob (#2)
+"
+        );
+    }
+
+    #[test]
+    fn dot_chart() {
+        let directory = company();
+
+        assert_eq!(
+            render(&directory, Some("Engineering/Platform"), ChartFormat::Dot),
+            r#"digraph org {
+    node [shape=box];
+    "department:Engineering/Platform" [shape=folder, label="Engineering/Platform"];
+    "employee:1" [label="Sally\n#1\nStaff Engineer"];
+    "department:Engineering/Platform" -> "employee:1";
+    "department:Engineering/Platform/Storage" [shape=folder, label="Storage"];
+    "department:Engineering/Platform" -> "department:Engineering/Platform/Storage";
+    "employee:2" [label="Bob\n#2"];
+    "department:Engineering/Platform/Storage" -> "employee:2";
+    "employee:3" [label="Amir\n#3"];
+    "employee:3" -> "department:Engineering/Platform" [style=dashed, label="manages"];
+}
+"#
+        );
+        assert!(
+            render(&directory, None, ChartFormat::Dot)
+                .contains(r#"[label="Dee \"D\" Jones\n#4"];"#)
+        );
+    }
+}
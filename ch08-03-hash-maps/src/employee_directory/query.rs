@@ -0,0 +1,257 @@
+use std::cmp::Ordering;
+
+use super::employee::{Date, Employee, EmployeeId, normalize};
+
+// A filtered, optionally sorted listing, as in
+// `List Engineering where title=Manager sort by start_date desc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub scope: Scope,
+    // Every condition has to hold.
+    pub conditions: Vec<Condition>,
+    // Without a sort, results come grouped by department and then ordered by name.
+    pub sort: Option<Sort>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    All,
+    Department(String),
+}
+
+impl Query {
+    pub fn new(scope: Scope) -> Query {
+        Query {
+            scope,
+            conditions: Vec::new(),
+            sort: None,
+        }
+    }
+
+    pub fn matches(&self, employee: &Employee) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(employee))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Id,
+    Name,
+    Department,
+    Title,
+    Email,
+    StartDate,
+}
+
+pub const FIELD_NAMES: &str = "id, name, department, title, email or start_date";
+
+impl Field {
+    pub fn from_name(name: &str) -> Option<Field> {
+        match name.to_ascii_lowercase().as_str() {
+            "id" => Some(Field::Id),
+            "name" => Some(Field::Name),
+            "department" => Some(Field::Department),
+            "title" => Some(Field::Title),
+            "email" => Some(Field::Email),
+            "start_date" => Some(Field::StartDate),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Id => "id",
+            Field::Name => "name",
+            Field::Department => "department",
+            Field::Title => "title",
+            Field::Email => "email",
+            Field::StartDate => "start_date",
+        }
+    }
+
+    // The field as it is shown, or None if the employee does not have it.
+    pub fn text(self, employee: &Employee) -> Option<String> {
+        match self {
+            Field::Id => Some(employee.id.to_string()),
+            Field::Name => Some(employee.name.clone()),
+            Field::Department => Some(employee.department.clone()),
+            Field::Title => employee.details.title.clone(),
+            Field::Email => employee.details.email.clone(),
+            Field::StartDate => employee.details.start_date.map(|date| date.to_string()),
+        }
+    }
+
+    // The field as it is compared: text ignores case, IDs and dates order numerically.
+    fn value(self, employee: &Employee) -> Option<Value> {
+        match self {
+            Field::Id => Some(Value::Id(employee.id)),
+            Field::StartDate => employee.details.start_date.map(Value::Date),
+            _ => self
+                .text(employee)
+                .map(|text| Value::Text(text.to_lowercase())),
+        }
+    }
+
+    // Reads a value typed in a condition, such as the "2021-03-01" of `start_date>2021-03-01`.
+    pub fn parse_value(self, text: &str) -> Result<Value, String> {
+        let text = normalize(text);
+        match self {
+            Field::Id => {
+                let digits = text.strip_prefix('#').unwrap_or(&text);
+                digits
+                    .parse()
+                    .map(|id| Value::Id(EmployeeId(id)))
+                    .map_err(|_| format!("'{text}' is not an employee ID"))
+            }
+            Field::StartDate => text
+                .parse()
+                .map(Value::Date)
+                .map_err(|error| error.to_string()),
+            _ => Ok(Value::Text(text.to_lowercase())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Value {
+    Id(EmployeeId),
+    Text(String),
+    Date(Date),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    pub fn from_symbol(symbol: &str) -> Option<Op> {
+        match symbol {
+            "=" => Some(Op::Eq),
+            "!=" => Some(Op::Ne),
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            ">" => Some(Op::Gt),
+            ">=" => Some(Op::Ge),
+            _ => None,
+        }
+    }
+}
+
+// `<field><op><value>`, as in `title=Manager` or `start_date>=2020-01-01`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: Op,
+    pub value: Value,
+}
+
+impl Condition {
+    // An employee without the field only matches `!=`.
+    pub fn matches(&self, employee: &Employee) -> bool {
+        let Some(value) = self.field.value(employee) else {
+            return self.op == Op::Ne;
+        };
+        let ordering = value.cmp(&self.value);
+        match self.op {
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Ne => ordering != Ordering::Equal,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort {
+    pub field: Field,
+    pub descending: bool,
+}
+
+impl Sort {
+    // Employees without the field come last whichever way the sort goes.
+    pub fn compare(&self, a: &Employee, b: &Employee) -> Ordering {
+        match (self.field.value(a), self.field.value(b)) {
+            (Some(a), Some(b)) if self.descending => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::employee_directory::employee::Details;
+
+    fn employee(id: u32, name: &str, title: Option<&str>, start_date: Option<&str>) -> Employee {
+        Employee {
+            id: EmployeeId(id),
+            name: name.to_string(),
+            department: "Engineering".to_string(),
+            details: Details {
+                title: title.map(str::to_string),
+                email: None,
+                start_date: start_date.map(|date| date.parse().unwrap()),
+            },
+        }
+    }
+
+    fn condition(field: Field, op: Op, value: &str) -> Condition {
+        Condition {
+            field,
+            op,
+            value: field.parse_value(value).unwrap(),
+        }
+    }
+
+    #[test]
+    fn conditions_compare_by_field_type() {
+        let sally = employee(3, "Sally", Some("Manager"), Some("2021-03-01"));
+        let amir = employee(12, "Amir", None, None);
+
+        assert!(condition(Field::Title, Op::Eq, "manager").matches(&sally));
+        assert!(!condition(Field::Title, Op::Eq, "manager").matches(&amir));
+        assert!(condition(Field::Title, Op::Ne, "manager").matches(&amir));
+        assert!(condition(Field::StartDate, Op::Lt, "2021-12-01").matches(&sally));
+        assert!(condition(Field::Id, Op::Gt, "#9").matches(&amir));
+        assert!(!condition(Field::Id, Op::Gt, "9").matches(&sally));
+        assert_eq!(
+            Field::StartDate.parse_value("March"),
+            Err("'March' is not a date, expected YYYY-MM-DD".to_string())
+        );
+    }
+
+    #[test]
+    fn sorts_put_missing_values_last() {
+        let early = employee(1, "Sally", None, Some("2019-06-01"));
+        let late = employee(2, "Amir", None, Some("2022-01-10"));
+        let unknown = employee(3, "Bob", None, None);
+        let mut employees = vec![&unknown, &early, &late];
+
+        let sort = Sort {
+            field: Field::StartDate,
+            descending: true,
+        };
+        employees.sort_by(|a, b| sort.compare(a, b));
+        assert_eq!(employees, [&late, &early, &unknown]);
+
+        let sort = Sort {
+            descending: false,
+            ..sort
+        };
+        employees.sort_by(|a, b| sort.compare(a, b));
+        assert_eq!(employees, [&early, &late, &unknown]);
+    }
+}
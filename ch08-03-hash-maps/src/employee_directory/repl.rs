@@ -0,0 +1,600 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use super::{
+    command::{Command, HELP},
+    directory::{Directory, DirectoryError},
+    employee::{Employee, EmployeeRef},
+    history::{self, History},
+    org_chart,
+    query::Query,
+    storage::{self, Format},
+};
+
+// Reads commands from `input` until Quit or the end of the input, applying them to the
+// directory in `history` and writing the replies to `output`. With `save_to`, the directory
+// is saved there after every change, and the new entries appended to the log next to it.
+//
+// Bad commands, failed changes and failed saves are reported and the loop carries on;
+// only I/O errors on `input` or `output` end it early.
+pub fn run<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    history: &mut History,
+    save_to: Option<&Path>,
+) -> io::Result<()> {
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+
+        let command = match line.parse::<Command>() {
+            Ok(command) => command,
+            Err(error) => {
+                writeln!(output, "Error: {error}")?;
+                continue;
+            }
+        };
+
+        let changed = match command {
+            Command::Add {
+                name,
+                department,
+                details,
+            } => match history.add_with(&name, &department, details) {
+                Ok(id) => {
+                    let directory = history.directory();
+                    let employee = directory.get(id).expect("just added");
+                    writeln!(
+                        output,
+                        "Added {} ({id}) to {}",
+                        employee.name, employee.department
+                    )?;
+                    for other in directory.named(&name) {
+                        if other.id != id {
+                            writeln!(
+                                output,
+                                "Note: {} ({}) in {} has the same name",
+                                other.name, other.id, other.department
+                            )?;
+                        }
+                    }
+                    true
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {error}")?;
+                    false
+                }
+            },
+            Command::Remove { employee } => match remove(history, &employee) {
+                Ok((employee, unmanaged)) => {
+                    writeln!(
+                        output,
+                        "Removed {} ({}) from {}",
+                        employee.name, employee.id, employee.department
+                    )?;
+                    for department in unmanaged {
+                        writeln!(output, "Note: {department} no longer has a manager")?;
+                    }
+                    true
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {error}")?;
+                    false
+                }
+            },
+            Command::Move { employee, to } => match history.move_employee(&employee, &to) {
+                Ok((employee, from)) => {
+                    writeln!(
+                        output,
+                        "Moved {} ({}) from {from} to {}",
+                        employee.name, employee.id, employee.department
+                    )?;
+                    true
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {error}")?;
+                    false
+                }
+            },
+            Command::Show { employee } => {
+                let directory = history.directory();
+                match directory.find(&employee) {
+                    Ok(employee) => write_employee(&mut output, directory, employee)?,
+                    Err(error) => writeln!(output, "Error: {error}")?,
+                }
+                false
+            }
+            Command::SetManager {
+                department,
+                manager,
+            } => match history.set_manager(&department, &manager) {
+                Ok((department, manager)) => {
+                    writeln!(
+                        output,
+                        "{} ({}) now manages {department}",
+                        manager.name, manager.id
+                    )?;
+                    true
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {error}")?;
+                    false
+                }
+            },
+            Command::UnsetManager { department } => match history.unset_manager(&department) {
+                Ok((department, Some(_))) => {
+                    writeln!(output, "{department} no longer has a manager")?;
+                    true
+                }
+                Ok((department, None)) => {
+                    writeln!(output, "{department} has no manager")?;
+                    false
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {error}")?;
+                    false
+                }
+            },
+            Command::Chart { department, format } => {
+                let directory = history.directory();
+                let root = department.map(|department| directory.resolve_department(&department));
+                match root.transpose() {
+                    _ if directory.is_empty() => writeln!(output, "The directory is empty")?,
+                    Ok(root) => write!(
+                        output,
+                        "{}",
+                        org_chart::render(directory, root.as_deref(), format)
+                    )?,
+                    Err(error) => writeln!(output, "Error: {error}")?,
+                }
+                false
+            }
+            Command::List(query) => {
+                let directory = history.directory();
+                match directory.query(&query) {
+                    Ok(employees) if employees.is_empty() && directory.is_empty() => {
+                        writeln!(output, "The directory is empty")?
+                    }
+                    Ok(employees) => write_listing(&mut output, &query, &employees)?,
+                    Err(DirectoryError::NoSuchDepartment(_)) => {
+                        writeln!(output, "No such department")?
+                    }
+                    Err(error) => writeln!(output, "Error: {error}")?,
+                }
+                false
+            }
+            Command::Count { by: None } => {
+                match history.directory().len() {
+                    1 => writeln!(output, "1 employee")?,
+                    count => writeln!(output, "{count} employees")?,
+                }
+                false
+            }
+            Command::Count { by: Some(field) } => {
+                let directory = history.directory();
+                if directory.is_empty() {
+                    writeln!(output, "The directory is empty")?;
+                }
+                for (value, count) in directory.count_by(field) {
+                    match value {
+                        Some(value) => writeln!(output, "{value}: {count}")?,
+                        None => writeln!(output, "(no {}): {count}", field.name())?,
+                    }
+                }
+                false
+            }
+            Command::Find { prefix } => {
+                let employees = history.directory().find_prefix(&prefix);
+                if employees.is_empty() {
+                    writeln!(output, "Nobody's name starts with {prefix}")?;
+                }
+                write_flat(&mut output, &employees)?;
+                false
+            }
+            Command::Import { path } => match import(history, Path::new(&path)) {
+                Ok((added, skipped)) => {
+                    writeln!(output, "Imported {added} employees from {path}")?;
+                    for error in &skipped {
+                        writeln!(output, "Skipped: {error}")?;
+                    }
+                    added > 0
+                }
+                Err(error) => {
+                    writeln!(output, "Error: {path}: {error}")?;
+                    false
+                }
+            },
+            Command::Export { path } => {
+                match export(history.directory(), Path::new(&path)) {
+                    Ok(()) => writeln!(output, "Exported the directory to {path}")?,
+                    Err(error) => writeln!(output, "Error: {path}: {error}")?,
+                }
+                false
+            }
+            Command::Undo => match history.undo() {
+                Ok(Some(events)) => {
+                    for event in events.iter().rev() {
+                        writeln!(output, "Undid: {event}")?;
+                    }
+                    true
+                }
+                Ok(None) => {
+                    writeln!(output, "Nothing to undo")?;
+                    false
+                }
+                Err(error) => {
+                    writeln!(output, "Error: could not replay the history: {error}")?;
+                    false
+                }
+            },
+            Command::Redo => match history.redo() {
+                Ok(Some(events)) => {
+                    for event in events {
+                        writeln!(output, "Redid: {event}")?;
+                    }
+                    true
+                }
+                Ok(None) => {
+                    writeln!(output, "Nothing to redo")?;
+                    false
+                }
+                Err(error) => {
+                    writeln!(output, "Error: could not replay the history: {error}")?;
+                    false
+                }
+            },
+            Command::History => {
+                let timeline = history.timeline();
+                if timeline.is_empty() {
+                    writeln!(output, "Nothing has changed yet")?;
+                }
+                for (at, moment) in timeline {
+                    writeln!(output, "{at}  {moment}")?;
+                }
+                false
+            }
+            Command::Help => {
+                writeln!(output, "{HELP}")?;
+                false
+            }
+            Command::Quit => return Ok(()),
+        };
+
+        if let (true, Some(path)) = (changed, save_to) {
+            let log = history::log_path(path);
+            if let Err(error) = history.save(&log) {
+                writeln!(
+                    output,
+                    "Error: could not save to {}: {error}",
+                    log.display()
+                )?;
+            }
+            if let Err(error) = history.directory().save(path) {
+                writeln!(
+                    output,
+                    "Error: could not save to {}: {error}",
+                    path.display()
+                )?;
+            }
+        }
+    }
+}
+
+// Also returns the departments that lost their manager along with the employee.
+fn remove(
+    history: &mut History,
+    employee: &EmployeeRef,
+) -> Result<(Employee, Vec<String>), DirectoryError> {
+    let directory = history.directory();
+    let id = directory.find(employee)?.id;
+    let managed: Vec<String> = directory
+        .managed_by(id)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let employee = history.remove(&EmployeeRef::Id(id))?;
+    Ok((employee, managed))
+}
+
+fn format_of(path: &Path) -> Result<Format, String> {
+    Format::from_path(path).ok_or_else(|| "expected a .csv or .json file".to_string())
+}
+
+// Reads the whole file before adding anyone, so a bad line leaves the directory untouched.
+// Returns how many employees were added and why the others were skipped.
+fn import(history: &mut History, path: &Path) -> Result<(usize, Vec<DirectoryError>), String> {
+    let records = storage::read_records(path, format_of(path)?).map_err(|e| e.to_string())?;
+    let skipped = history.import(&records);
+    Ok((records.len() - skipped.len(), skipped))
+}
+
+fn export(directory: &Directory, path: &Path) -> Result<(), String> {
+    directory
+        .export(path, format_of(path)?)
+        .map_err(|e| e.to_string())
+}
+
+// Unsorted results are grouped under their departments, like the plain listing; sorted
+// results from several departments are listed one per line with the department in front.
+fn write_listing<W: Write>(
+    output: &mut W,
+    query: &Query,
+    employees: &[&Employee],
+) -> io::Result<()> {
+    let one_department = employees
+        .windows(2)
+        .all(|pair| pair[0].department == pair[1].department);
+    match employees {
+        [] => writeln!(output, "No one matches"),
+        _ if query.sort.is_none() => {
+            for group in employees.chunk_by(|a, b| a.department == b.department) {
+                write_department(output, &group[0].department, group)?;
+            }
+            Ok(())
+        }
+        [first, ..] if one_department => write_department(output, &first.department, employees),
+        _ => write_flat(output, employees),
+    }
+}
+
+fn write_flat<W: Write>(output: &mut W, employees: &[&Employee]) -> io::Result<()> {
+    for employee in employees {
+        writeln!(output, "    {}: {employee}", employee.department)?;
+    }
+    Ok(())
+}
+
+fn write_department<W: Write>(
+    output: &mut W,
+    department: &str,
+    employees: &[&Employee],
+) -> io::Result<()> {
+    writeln!(output, "{department}:")?;
+    for employee in employees {
+        writeln!(output, "    {employee}")?;
+    }
+    Ok(())
+}
+
+fn write_employee<W: Write>(
+    output: &mut W,
+    directory: &Directory,
+    employee: &Employee,
+) -> io::Result<()> {
+    writeln!(output, "{} ({})", employee.name, employee.id)?;
+    writeln!(output, "    Department: {}", employee.department)?;
+    if let Some(manager) = directory.manager_of(employee.id) {
+        writeln!(output, "    Reports to: {} ({})", manager.name, manager.id)?;
+    }
+    let managed = directory.managed_by(employee.id);
+    if !managed.is_empty() {
+        writeln!(output, "    Manages:    {}", managed.join(", "))?;
+    }
+    if let Some(title) = &employee.details.title {
+        writeln!(output, "    Title:      {title}")?;
+    }
+    if let Some(email) = &employee.details.email {
+        writeln!(output, "    Email:      {email}")?;
+    }
+    if let Some(start_date) = &employee.details.start_date {
+        writeln!(output, "    Started:    {start_date}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::employee_directory::{employee::EmployeeId, history::Timestamp};
+    use std::io::Cursor;
+
+    fn clock() -> Timestamp {
+        Timestamp(1_760_000_000)
+    }
+
+    fn session(input: &str) -> (Directory, String) {
+        let mut history = History::with_clock(Directory::new(), clock);
+        let mut output = Vec::new();
+        run(Cursor::new(input), &mut output, &mut history, None).unwrap();
+        (
+            history.directory().clone(),
+            String::from_utf8(output).unwrap(),
+        )
+    }
+
+    #[test]
+    fn add_move_and_list() {
+        let (_, output) = session(
+            "Add Sally to Engineering title=Manager\nAdd Amir to Sales\n\
+             Move Amir from Sales to Engineering\nList All\n",
+        );
+
+        assert!(output.contains("Added Sally (#1) to Engineering\n"));
+        assert!(output.contains("Moved Amir (#2) from Sales to Engineering\n"));
+        assert!(output.contains("Engineering:\n    Amir (#2)\n    Sally (#1), Manager\n"));
+    }
+
+    #[test]
+    fn multi_word_names_ids_and_duplicates() {
+        let (directory, output) = session(
+            "Add Mary Jane to Human Resources email=mj@example.com\n\
+             Add mary jane to Human Resources\n\
+             Add Mary Jane to Engineering\n\
+             Remove Mary Jane\n\
+             Show Mary Jane in Human Resources\n\
+             Remove #2\n",
+        );
+
+        assert!(output.contains("Added Mary Jane (#1) to Human Resources\n"));
+        assert!(output.contains("Error: Mary Jane already works in Human Resources as #1\n"));
+        assert!(output.contains("Note: Mary Jane (#1) in Human Resources has the same name\n"));
+        assert!(output.contains("several people called Mary Jane (#1, #2)"));
+        assert!(output.contains(
+            "Mary Jane (#1)\n    Department: Human Resources\n    Email:      mj@example.com\n"
+        ));
+        assert!(output.contains("Removed Mary Jane (#2) from Engineering\n"));
+        assert_eq!(directory.all().len(), 1);
+    }
+
+    #[test]
+    fn queries() {
+        let (_, output) = session(
+            "Add Sally to Engineering title=Manager start_date=2019-06-01\n\
+             Add Ana to Engineering title=Manager start_date=2022-01-10\n\
+             Add Bob to Engineering\n\
+             Add Amir to Sales title=Manager\n\
+             List Engineering where title=manager sort by start_date desc\n\
+             List All where title!=Manager\n\
+             List All where title=Manager sort by name\n\
+             List Sales where title=Clerk\n\
+             Count by department\n\
+             Count by title\n\
+             Count\n\
+             Find a\n\
+             Find Zed\n",
+        );
+
+        assert!(output.contains(
+            "Engineering:\n    Ana (#2), Manager, since 2022-01-10\n    \
+             Sally (#1), Manager, since 2019-06-01\n"
+        ));
+        assert!(output.contains("> Engineering:\n    Bob (#3)\n> "));
+        assert!(output.contains(
+            "    Sales: Amir (#4), Manager\n    Engineering: Ana (#2), Manager, since 2022-01-10\n"
+        ));
+        assert!(output.contains("No one matches\n"));
+        assert!(output.contains("Engineering: 3\nSales: 1\n"));
+        assert!(output.contains("(no title): 1\nManager: 3\n"));
+        assert!(output.contains(
+            "4 employees\n>     Sales: Amir (#4), Manager\n    \
+             Engineering: Ana (#2), Manager, since 2022-01-10\n> \
+             Nobody's name starts with Zed\n"
+        ));
+    }
+
+    #[test]
+    fn department_tree_and_managers() {
+        let (directory, output) = session(
+            "Add Amir to Engineering\n\
+             Add Sally to Engineering/Platform\n\
+             Add Bob to Engineering/Platform/Storage\n\
+             Add Dee to Platform\n\
+             Set manager of Platform to Amir\n\
+             Set manager of Storage to Bob\n\
+             Show Bob\n\
+             Show Sally\n\
+             List Platform\n\
+             Chart Platform\n\
+             Remove Amir\n\
+             Set manager of Sales to Bob\n",
+        );
+
+        assert!(output.contains("Added Dee (#4) to Engineering/Platform\n"));
+        assert!(output.contains("Amir (#1) now manages Engineering/Platform\n"));
+        assert!(output.contains(
+            "Bob (#3)\n    Department: Engineering/Platform/Storage\n    \
+             Reports to: Amir (#1)\n    Manages:    Engineering/Platform/Storage\n"
+        ));
+        assert!(output.contains("Department: Engineering/Platform\n    Reports to: Amir (#1)\n"));
+        assert!(output.contains(
+            "Engineering/Platform:\n    Dee (#4)\n    Sally (#2)\n\
+             Engineering/Platform/Storage:\n    Bob (#3)\n"
+        ));
+        assert!(output.contains(
+            "Engineering/Platform/ [manager: Amir (#1)]\n    Dee (#4)\n    Sally (#2)\n    \
+             Storage/ [manager: Bob (#3)]\n        Bob (#3)\n"
+        ));
+        assert!(output.contains("Note: Engineering/Platform no longer has a manager\n"));
+        assert!(output.contains("Error: there is no department called Sales\n"));
+        assert_eq!(directory.managers().count(), 1);
+    }
+
+    #[test]
+    fn undo_redo_and_history() {
+        let (directory, output) = session(
+            "History\n\
+             Undo\n\
+             Add Sally to Engineering\n\
+             Add Amir to Sales\n\
+             Move Amir to Engineering\n\
+             Undo\n\
+             Undo\n\
+             Redo\n\
+             Redo\n\
+             Redo\n\
+             Add Bob to Sales\n\
+             History\n",
+        );
+
+        assert!(output.contains("> Nothing has changed yet\n> Nothing to undo\n"));
+        assert!(output.contains("Undid: Moved Amir (#2) from Sales to Engineering\n"));
+        assert!(output.contains("Undid: Added Amir (#2) to Sales\n"));
+        assert!(output.contains("Redid: Moved Amir (#2) from Sales to Engineering\n"));
+        assert!(output.contains("Nothing to redo\n"));
+        assert!(output.contains(
+            "2025-10-09 08:53:20  Added Sally (#1) to Engineering\n\
+             2025-10-09 08:53:20  Added Amir (#2) to Sales\n\
+             2025-10-09 08:53:20  Moved Amir (#2) from Sales to Engineering\n\
+             2025-10-09 08:53:20  Undid: Moved Amir (#2) from Sales to Engineering\n\
+             2025-10-09 08:53:20  Undid: Added Amir (#2) to Sales\n\
+             2025-10-09 08:53:20  Redid: Added Amir (#2) to Sales\n\
+             2025-10-09 08:53:20  Redid: Moved Amir (#2) from Sales to Engineering\n\
+             2025-10-09 08:53:20  Added Bob (#3) to Sales\n"
+        ));
+        assert_eq!(directory.len(), 3);
+    }
+
+    #[test]
+    fn reports_errors_and_keeps_going() {
+        let (_, output) = session("Hire Sally\nRemove Sally from Sales\nList Sales\nHelp\n");
+
+        assert!(output.contains("Error: unknown command 'Hire'"));
+        assert!(output.contains("Error: there is no department called Sales"));
+        assert!(output.contains("No such department"));
+        assert!(output.contains("Commands:"));
+    }
+
+    #[test]
+    fn quit_stops_reading() {
+        let (directory, output) = session("Quit\nAdd Sally to Engineering\n");
+
+        assert!(directory.is_empty());
+        assert_eq!(output, "> ");
+    }
+
+    #[test]
+    fn saves_after_every_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("directory.json");
+        let log = history::log_path(&path);
+
+        let mut history = History::new(Directory::new());
+        let input = "Add Sally to Engineering\nAdd Amir to Sales\nRemove Amir from Sales\n\
+                     Undo\nList All\n";
+        run(Cursor::new(input), Vec::new(), &mut history, Some(&path)).unwrap();
+
+        let (saved, _) = Directory::load(&path).unwrap();
+        assert_eq!(&saved, history.directory());
+        assert_eq!(saved.next_id(), EmployeeId(3));
+        let (replayed, _) = History::load(&log).unwrap();
+        assert_eq!(replayed.directory(), history.directory());
+        assert_eq!(replayed.entries().len(), 5);
+    }
+
+    #[test]
+    fn import_needs_a_known_format() {
+        let (directory, output) = session("Import staff.txt\nImport missing.csv\n");
+
+        assert!(directory.is_empty());
+        assert!(output.contains("Error: staff.txt: expected a .csv or .json file"));
+        assert!(output.contains("Error: missing.csv: "));
+    }
+}
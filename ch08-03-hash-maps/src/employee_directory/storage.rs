@@ -0,0 +1,586 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{
+    directory::{Directory, DirectoryError},
+    employee::{Date, Details, Employee, EmployeeId, EmployeeRef, normalize},
+};
+
+pub const DEFAULT_DIRECTORY_FILE: &str = "employee-directory.json";
+
+// One employee as stored on disk and exchanged through CSV and JSON. Only `name` and
+// `department` are required, so files written before IDs existed still load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<EmployeeId>,
+    pub name: String,
+    pub department: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<Date>,
+}
+
+impl From<&Employee> for Record {
+    fn from(employee: &Employee) -> Self {
+        Record {
+            id: Some(employee.id),
+            name: employee.name.clone(),
+            department: employee.department.clone(),
+            title: employee.details.title.clone(),
+            email: employee.details.email.clone(),
+            start_date: employee.details.start_date,
+        }
+    }
+}
+
+impl Record {
+    pub(super) fn details(&self) -> Details {
+        Details {
+            title: self.title.clone(),
+            email: self.email.clone(),
+            start_date: self.start_date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Document {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next_id: Option<EmployeeId>,
+    employees: Vec<Record>,
+    // Department path to the ID of its manager.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    managers: BTreeMap<String, EmployeeId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    // Picks the format from the file extension, so `Export staff.csv` does what it says.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+// What `load` found at the path it was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadStatus {
+    // No file yet, so the directory starts empty.
+    Missing,
+    Loaded,
+    // The file could not be read as a directory. It was renamed to `backup` so the data
+    // can be recovered by hand, and the directory starts empty.
+    Recovered { backup: PathBuf },
+}
+
+impl Directory {
+    // Every employee in ID order.
+    pub fn records(&self) -> Vec<Record> {
+        self.employees().map(Record::from).collect()
+    }
+
+    // Adds every record to the directory as a new employee, next to the ones already in it.
+    // IDs in the records are ignored, since they come from some other system. Records that
+    // would duplicate someone are skipped and returned as errors.
+    pub fn import(&mut self, records: &[Record]) -> Vec<DirectoryError> {
+        records
+            .iter()
+            .filter_map(|record| {
+                self.add_with(&record.name, &record.department, record.details())
+                    .err()
+            })
+            .collect()
+    }
+
+    // Saved employees keep their IDs; ones saved before IDs existed get new ones.
+    fn from_document(document: Document) -> Result<Directory, DirectoryError> {
+        let (saved, unnumbered): (Vec<Record>, Vec<Record>) = document
+            .employees
+            .into_iter()
+            .partition(|record| record.id.is_some());
+
+        let employees = saved.into_iter().map(|record| Employee {
+            id: record.id.expect("partitioned on the ID"),
+            details: record.details(),
+            name: record.name,
+            department: record.department,
+        });
+        let mut directory =
+            Directory::restore(employees, document.next_id.unwrap_or(EmployeeId(1)))?;
+        for record in unnumbered {
+            directory.add_with(&record.name, &record.department, record.details())?;
+        }
+        for (department, manager) in document.managers {
+            directory.set_manager(&format!("/{department}"), &EmployeeRef::Id(manager))?;
+        }
+        Ok(directory)
+    }
+
+    fn document(&self) -> Document {
+        Document {
+            next_id: Some(self.next_id()),
+            employees: self.records(),
+            managers: self
+                .managers()
+                .map(|(department, id)| (department.to_string(), id))
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<(Directory, LoadStatus), StorageError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok((Directory::new(), LoadStatus::Missing));
+            }
+            Err(error) => return Err(StorageError::Io(error)),
+        };
+
+        let directory = serde_json::from_str(&json)
+            .ok()
+            .and_then(|document| Directory::from_document(document).ok());
+        match directory {
+            Some(directory) => Ok((directory, LoadStatus::Loaded)),
+            None => {
+                let backup = backup_path(path);
+                fs::rename(path, &backup)?;
+                Ok((Directory::new(), LoadStatus::Recovered { backup }))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        write_atomically(path, &serde_json::to_string_pretty(&self.document())?)
+    }
+
+    // A JSON export is a complete copy of the saved directory, managers included, so it
+    // doubles as a backup. A CSV export only has the employees.
+    pub fn export(&self, path: &Path, format: Format) -> Result<(), StorageError> {
+        let contents = match format {
+            Format::Json => serde_json::to_string_pretty(&self.document())?,
+            Format::Csv => to_csv(&self.records()),
+        };
+        write_atomically(path, &contents)
+    }
+}
+
+// Reads records from a JSON or CSV file without touching any directory.
+pub fn read_records(path: &Path, format: Format) -> Result<Vec<Record>, StorageError> {
+    let contents = fs::read_to_string(path)?;
+    match format {
+        Format::Json => from_json(&contents),
+        Format::Csv => from_csv(&contents),
+    }
+}
+
+// Writes to a temporary file next to the target and renames it into place,
+// so a crash mid-write never leaves a half-written directory behind.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), StorageError> {
+    let tmp = sibling(path, ".tmp");
+
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+pub(super) fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// Where to move a corrupt file: "x.json.corrupt", or "x.json.corrupt.2" and so on, so that
+// an earlier backup is never overwritten.
+pub(super) fn backup_path(path: &Path) -> PathBuf {
+    std::iter::once(sibling(path, ".corrupt"))
+        .chain((2..).map(|n| sibling(path, &format!(".corrupt.{n}"))))
+        .find(|backup| !backup.exists())
+        .expect("the counter runs out long after the disk does")
+}
+
+pub fn from_json(json: &str) -> Result<Vec<Record>, StorageError> {
+    let document: Document = serde_json::from_str(json)?;
+    Ok(document.employees)
+}
+
+const CSV_COLUMNS: [&str; 6] = ["id", "name", "department", "title", "email", "start_date"];
+
+// A header line followed by one line per employee, with empty fields for missing details.
+// Fields containing a comma, a quote or a line break are quoted, with quotes doubled,
+// the way spreadsheets write them.
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+    csv.push('\n');
+    for record in records {
+        let id = record.id.map(|id| id.0.to_string());
+        let start_date = record.start_date.map(|date| date.to_string());
+        let fields = [
+            id.as_deref(),
+            Some(record.name.as_str()),
+            Some(record.department.as_str()),
+            record.title.as_deref(),
+            record.email.as_deref(),
+            start_date.as_deref(),
+        ];
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|field| csv_field(field.unwrap_or("")))
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// The header must have "name" and "department" columns, and may have "id", "title", "email"
+// and "start_date" ones, in any order and any case; other columns, as an HR export usually
+// has, are ignored. Blank lines are skipped, and so are empty optional fields.
+pub fn from_csv(csv: &str) -> Result<Vec<Record>, StorageError> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_line, header) = lines.next().ok_or(StorageError::Csv {
+        line: 1,
+        message: "the file is empty, expected a header line".to_string(),
+    })?;
+    let header = parse_csv_line(header, header_line)?;
+    let column = |wanted: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(wanted))
+    };
+    let required = |wanted: &str| {
+        column(wanted).ok_or_else(|| StorageError::Csv {
+            line: header_line,
+            message: format!("the header has no '{wanted}' column"),
+        })
+    };
+    let (name_column, department_column) = (required("name")?, required("department")?);
+    let [id_column, title_column, email_column, start_date_column] =
+        ["id", "title", "email", "start_date"].map(column);
+
+    let mut records = Vec::new();
+    for (line_number, line) in lines {
+        let fields = parse_csv_line(line, line_number)?;
+        let error = |message: String| StorageError::Csv {
+            line: line_number,
+            message,
+        };
+        let optional = |index: Option<usize>| {
+            let value = normalize(fields.get(index?)?);
+            Some(value).filter(|value| !value.is_empty())
+        };
+        let required = |index: usize, wanted: &str| {
+            optional(Some(index)).ok_or_else(|| error(format!("missing {wanted}")))
+        };
+
+        let id = optional(id_column)
+            .map(|id| {
+                let number = id.strip_prefix('#').unwrap_or(&id);
+                number
+                    .parse()
+                    .map(EmployeeId)
+                    .map_err(|_| error(format!("'{id}' is not an employee ID")))
+            })
+            .transpose()?;
+        let start_date = optional(start_date_column)
+            .map(|date| date.parse::<Date>().map_err(|e| error(e.to_string())))
+            .transpose()?;
+
+        records.push(Record {
+            id,
+            name: required(name_column, "name")?,
+            department: required(department_column, "department")?,
+            title: optional(title_column),
+            email: optional(email_column),
+            start_date,
+        });
+    }
+
+    Ok(records)
+}
+
+fn parse_csv_line(line: &str, line_number: usize) -> Result<Vec<String>, StorageError> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(StorageError::Csv {
+            line: line_number,
+            message: "a quoted field is never closed".to_string(),
+        });
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Csv { line: usize, message: String },
+}
+
+impl From<io::Error> for StorageError {
+    fn from(error: io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(error: serde_json::Error) -> Self {
+        StorageError::Json(error)
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(error) => write!(f, "{error}"),
+            StorageError::Json(error) => write!(f, "{error}"),
+            StorageError::Csv { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Directory {
+        let mut directory = Directory::new();
+        let details = Details {
+            title: Some("Staff Engineer".to_string()),
+            email: Some("sally@example.com".to_string()),
+            start_date: "2021-03-01".parse().ok(),
+        };
+        directory.add_with("Sally", "Engineering", details).unwrap();
+        directory.add("Amir", "Sales").unwrap();
+        directory.add("O'Neil, Pat", "Sales").unwrap();
+        directory
+    }
+
+    fn record(name: &str, department: &str) -> Record {
+        Record {
+            id: None,
+            name: name.to_string(),
+            department: department.to_string(),
+            title: None,
+            email: None,
+            start_date: None,
+        }
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("round-trip.json");
+        assert_eq!(
+            Directory::load(&path).unwrap(),
+            (Directory::new(), LoadStatus::Missing)
+        );
+
+        let mut directory = sample();
+        directory.remove(&EmployeeRef::Id(EmployeeId(3))).unwrap();
+        directory
+            .set_manager("Engineering", &EmployeeRef::Id(EmployeeId(2)))
+            .unwrap();
+        directory.save(&path).unwrap();
+        assert!(!sibling(&path, ".tmp").exists());
+
+        let (loaded, status) = Directory::load(&path).unwrap();
+        assert_eq!(status, LoadStatus::Loaded);
+        assert_eq!(loaded, directory);
+        assert_eq!(loaded.next_id(), EmployeeId(4));
+    }
+
+    #[test]
+    fn loads_files_without_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("old.json");
+        fs::write(
+            &path,
+            r#"{"employees": [{"name": "Sally", "department": "Engineering"}]}"#,
+        )
+        .unwrap();
+
+        let (directory, status) = Directory::load(&path).unwrap();
+        assert_eq!(status, LoadStatus::Loaded);
+        assert_eq!(directory.get(EmployeeId(1)).unwrap().name, "Sally");
+    }
+
+    #[test]
+    fn corrupt_file_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corrupt.json");
+        fs::write(&path, "not json").unwrap();
+
+        let (directory, status) = Directory::load(&path).unwrap();
+        let backup = sibling(&path, ".corrupt");
+
+        assert!(directory.is_empty());
+        assert_eq!(
+            status,
+            LoadStatus::Recovered {
+                backup: backup.clone()
+            }
+        );
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "not json");
+
+        fs::write(&path, "still not json").unwrap();
+        let (_, status) = Directory::load(&path).unwrap();
+        assert_eq!(
+            status,
+            LoadStatus::Recovered {
+                backup: sibling(&path, ".corrupt.2")
+            }
+        );
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "not json");
+    }
+
+    #[test]
+    fn the_last_id_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("full.json");
+        let json =
+            r#"{"employees": [{"id": 4294967295, "name": "Sally", "department": "Engineering"}]}"#;
+        fs::write(&path, json).unwrap();
+
+        let (directory, status) = Directory::load(&path).unwrap();
+        assert!(directory.is_empty());
+        assert!(matches!(status, LoadStatus::Recovered { .. }));
+    }
+
+    #[test]
+    fn csv_round_trip_with_quoting() {
+        let csv = to_csv(&sample().records());
+
+        assert!(csv.starts_with("id,name,department,title,email,start_date\n"));
+        assert!(csv.contains("1,Sally,Engineering,Staff Engineer,sally@example.com,2021-03-01\n"));
+        assert!(csv.contains("3,\"O'Neil, Pat\",Sales,,,\n"));
+        assert_eq!(from_csv(&csv).unwrap(), sample().records());
+    }
+
+    #[test]
+    fn csv_from_a_spreadsheet() {
+        let csv = "Employee ID,Department,Name,Title\n7,Engineering,Sally,\n\n\
+                   8,\"Sales\",\"Amir \"\"A\"\" Khan\",Account  Manager\n";
+
+        assert_eq!(
+            from_csv(csv).unwrap(),
+            [
+                record("Sally", "Engineering"),
+                Record {
+                    title: Some("Account Manager".to_string()),
+                    ..record("Amir \"A\" Khan", "Sales")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let error = |csv| from_csv(csv).unwrap_err().to_string();
+
+        assert_eq!(
+            error("name\nSally\n"),
+            "line 1: the header has no 'department' column"
+        );
+        assert_eq!(
+            error("name,department\nSally,\n"),
+            "line 2: missing department"
+        );
+        assert_eq!(
+            error("name,department\n\"Sally,Engineering\n"),
+            "line 2: a quoted field is never closed"
+        );
+        assert_eq!(
+            error("name,department,start_date\nSally,Sales,2021\n"),
+            "line 2: '2021' is not a date, expected YYYY-MM-DD"
+        );
+    }
+
+    #[test]
+    fn export_and_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        let format = Format::from_path(&path).unwrap();
+        sample().export(&path, format).unwrap();
+
+        let mut directory = Directory::new();
+        directory.add("Bob", "Engineering").unwrap();
+        directory.add("Amir", "Sales").unwrap();
+        let skipped = directory.import(&read_records(&path, format).unwrap());
+
+        assert_eq!(
+            skipped,
+            [DirectoryError::Duplicate {
+                name: "Amir".to_string(),
+                department: "Sales".to_string(),
+                id: EmployeeId(2)
+            }]
+        );
+        let sally = directory.named("Sally")[0];
+        assert_eq!(sally.id, EmployeeId(3));
+        assert_eq!(sally.details.title.as_deref(), Some("Staff Engineer"));
+        assert_eq!(
+            Format::from_path(Path::new("staff.JSON")),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_path(Path::new("staff.txt")), None);
+    }
+}
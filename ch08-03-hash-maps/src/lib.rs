@@ -1,3 +1,5 @@
 pub mod employee_directory;
 pub mod pig_latin;
 pub mod statistics;
+pub mod tokenizer;
+pub mod word_games;
@@ -146,11 +146,21 @@ fn main() {
 fn excercise1() {
     // Given a list of integers, use a vector and return the median (when sorted, the value in the middle position) and mode (the value that occurs most often; a hash map will be helpful here) of the list.
 
-    let mut integers = vec![3, 4, 8, 2, 2, 5, 0, 9, 6, 6, 1, 10, 2];
-    let median = statistics::median(&mut integers);
-    let (mode, count) = statistics::mode(&mut integers);
+    let integers = vec![3, 4, 8, 2, 2, 5, 0, 9, 6, 6, 1, 10, 2];
+
+    // Both functions borrow the data as a slice, so `integers` keeps its original order.
+    match (statistics::median(&integers), statistics::modes(&integers)) {
+        (Ok(median), Ok(modes)) => println!(
+            "{integers:?}, median = {median}, mode = {:?} with count : {}",
+            modes.values, modes.count
+        ),
+        (Err(error), _) | (_, Err(error)) => println!("{integers:?}: {error}"),
+    }
 
-    println!("{integers:?}, median = {median}, mode = {mode} with count : {count}");
+    match statistics::descriptive::Summary::from_slice(&integers) {
+        Ok(summary) => println!("{summary}{}", summary.to_json()),
+        Err(error) => println!("{integers:?}: {error}"),
+    }
 }
 
 fn excercise2() {
@@ -161,6 +171,12 @@ fn excercise2() {
     let pig_latin_str = pig_latin::convert_to_pig_latin(&str);
 
     println!("{pig_latin_str}");
+
+    // Punctuation, spacing and capitalization survive the conversion.
+    println!(
+        "{}",
+        pig_latin::convert_to_pig_latin("Hello, world! The quick rhythm of école.")
+    );
 }
 
 fn excercise3() {
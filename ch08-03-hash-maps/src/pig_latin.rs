@@ -1,29 +1,339 @@
-pub fn convert_to_pig_latin(sentence: &String) -> String {
-    let vowels = ['a', 'e', 'i', 'o', 'u'];
-
-    let mut result: Vec<String> = Vec::new();
-
-    for word in sentence.split_whitespace() {
-        let chars: Vec<char> = word.chars().collect();
-
-        for (index, letter) in word.char_indices() {
-            if vowels.contains(&letter.to_ascii_lowercase()) {
-                if index == 0 {
-                    result.push(format!("{word}-hay"));
-                    break;
-                } else {
-                    let head: String = chars[..index].iter().collect();
-                    let tail: String = chars[index..].iter().collect();
-                    result.push(format!("{tail}-{head}ay"));
-                    break;
-                }
+use std::fmt;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::tokenizer::{Case, Token, capitalize, decapitalize, tokenize};
+
+// Vowels are matched on the lowercased first char of each grapheme, so both a precomposed "é"
+// and an "e" followed by a combining accent count. "y" is handled separately in `split_point`.
+pub(crate) const VOWELS: &str = "aeiouàáâãäåāăąæèéêëēĕėęěìíîïĩīĭįıòóôõöøōŏőœùúûüũūŭůűų";
+
+// Converts every word in `text` to Pig Latin and leaves everything else exactly where it was.
+//
+// Words are found with Unicode word boundaries (UAX #29), so punctuation, digits and
+// whitespace, including runs of spaces and newlines, come through unchanged:
+// "Hello, world!" becomes "Ello-hay, orld-way!".
+pub fn convert_to_pig_latin(text: &str) -> String {
+    tokenize(text)
+        .map(|token| match token {
+            Token::Word(word) => word_to_pig_latin(word),
+            other => other.as_str().to_string(),
+        })
+        .collect()
+}
+
+// A word is translated when it starts with a cased letter. Numbers like "42" are left alone,
+// and so are scripts without letter case, such as Chinese, which have no vowels to find.
+pub(crate) fn is_word(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_lowercase() || c.is_uppercase())
+}
+
+// Converts a single word:
+//
+// - a word starting with a vowel gets "-hay": "apple" becomes "apple-hay"
+// - otherwise the leading consonants move to the end followed by "ay": "string" becomes "ing-stray"
+// - "qu" moves as one consonant: "queen" becomes "een-quay", "square" becomes "are-squay"
+// - "y" is a consonant at the start of a word and a vowel anywhere else:
+//   "yellow" becomes "ellow-yay" and "rhythm" becomes "ythm-rhay"
+// - a word with no vowel at all just gets "-ay": "hmm" becomes "hmm-ay"
+//
+// Capitalized words stay capitalized ("Hello" becomes "Ello-hay") and all-caps words
+// stay all-caps ("HELLO" becomes "ELLO-HAY"). Other mixes of case are kept letter by letter.
+// Anything that does not start with a cased letter is returned unchanged.
+pub fn word_to_pig_latin(word: &str) -> String {
+    if !is_word(word) {
+        return word.to_string();
+    }
+
+    match Case::of(word) {
+        Case::Title => capitalize(&convert_word(&decapitalize(word))),
+        Case::Upper => convert_word(&word.to_lowercase()).to_uppercase(),
+        Case::Other => convert_word(word),
+    }
+}
+
+fn convert_word(word: &str) -> String {
+    let graphemes: Vec<&str> = word.graphemes(true).collect();
+
+    match split_point(&graphemes) {
+        Some(0) => format!("{word}-hay"),
+        Some(index) => {
+            let (head, tail) = graphemes.split_at(index);
+            format!("{}-{}ay", tail.concat(), head.concat())
+        }
+        None => format!("{word}-ay"),
+    }
+}
+
+// The index of the first grapheme that belongs to the tail, or None when the word has no vowel.
+fn split_point(graphemes: &[&str]) -> Option<usize> {
+    let letters: Vec<char> = graphemes
+        .iter()
+        .map(|grapheme| base_letter(grapheme))
+        .collect();
+
+    (0..letters.len()).find(|&index| {
+        let letter = letters[index];
+        let after_q = index > 0 && letters[index - 1] == 'q';
+
+        match letter {
+            'u' if after_q => false,
+            'y' => index > 0,
+            _ => VOWELS.contains(letter),
+        }
+    })
+}
+
+pub(crate) fn base_letter(grapheme: &str) -> char {
+    grapheme
+        .chars()
+        .next()
+        .and_then(|letter| letter.to_lowercase().next())
+        .unwrap_or_default()
+}
+
+// Decodes text produced by `convert_to_pig_latin`, leaving punctuation and whitespace in place.
+//
+// Every word must have the "tail-headay", "word-hay" or "word-ay" shape, and the split must be one
+// the encoder would have made: "tring-say" is rejected because "string" moves "str" as a whole.
+//
+// One case can't be told apart: a word starting with "h" and a vowel encodes the same way as the
+// word without the "h" ("hello" and "ello" both become "ello-hay"). The decoder always picks
+// the word without the "h". Everything else that is lowercase, capitalized or all caps decodes
+// back to exactly the original text.
+pub fn from_pig_latin(text: &str) -> Result<String, DecodeError> {
+    let mut decoded = String::new();
+    let mut tokens = text.split_word_bound_indices();
+
+    while let Some((offset, token)) = tokens.next() {
+        if !is_word(token) {
+            decoded.push_str(token);
+            continue;
+        }
+
+        // The hyphen and the suffix are separate tokens, so look ahead without consuming them.
+        let mut lookahead = tokens.clone();
+        let suffix = match (lookahead.next(), lookahead.next()) {
+            (Some((_, "-")), Some((_, suffix))) if is_word(suffix) => suffix,
+            _ => {
+                return Err(DecodeError {
+                    token: token.to_string(),
+                    offset,
+                    kind: DecodeErrorKind::MissingSuffix,
+                });
             }
+        };
+        tokens = lookahead;
+
+        let word = word_from_pig_latin(token, suffix).map_err(|kind| DecodeError {
+            token: format!("{token}-{suffix}"),
+            offset,
+            kind,
+        })?;
+        decoded.push_str(&word);
+    }
+
+    Ok(decoded)
+}
+
+fn word_from_pig_latin(tail: &str, suffix: &str) -> Result<String, DecodeErrorKind> {
+    match Case::of(&format!("{tail}-{suffix}")) {
+        Case::Title => decode_word(&decapitalize(tail), suffix).map(|word| capitalize(&word)),
+        Case::Upper => decode_word(&tail.to_lowercase(), &suffix.to_lowercase())
+            .map(|word| word.to_uppercase()),
+        Case::Other => decode_word(tail, suffix),
+    }
+}
+
+fn decode_word(tail: &str, suffix: &str) -> Result<String, DecodeErrorKind> {
+    let head = suffix
+        .strip_suffix("ay")
+        .ok_or(DecodeErrorKind::MissingAy)?;
+    let tail_split = split_point(&tail.graphemes(true).collect::<Vec<_>>());
 
-            if index == chars.len() - 1 {
-                result.push(word.to_string());
+    if head.is_empty() && tail_split.is_none() {
+        return Ok(tail.to_string());
+    }
+    if head == "h" && tail_split == Some(0) {
+        return Ok(tail.to_string());
+    }
+
+    let word = format!("{head}{tail}");
+    let head_length = head.graphemes(true).count();
+    if head_length > 0
+        && split_point(&word.graphemes(true).collect::<Vec<_>>()) == Some(head_length)
+    {
+        Ok(word)
+    } else {
+        Err(DecodeErrorKind::NotEncoderOutput)
+    }
+}
+
+// A word that `from_pig_latin` could not decode, with its byte offset in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub token: String,
+    pub offset: usize,
+    pub kind: DecodeErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    // A word without a hyphenated suffix, like "hello".
+    MissingSuffix,
+    // The suffix does not end in "ay", like "ello-hey".
+    MissingAy,
+    // Shaped like Pig Latin, but not something the encoder produces, like "tring-say" or "apple-ay".
+    NotEncoderOutput,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            DecodeErrorKind::MissingSuffix => "the word has no \"-...ay\" suffix",
+            DecodeErrorKind::MissingAy => "the suffix does not end in \"ay\"",
+            DecodeErrorKind::NotEncoderOutput => "the consonants were not split the Pig Latin way",
+        };
+        write!(
+            f,
+            "'{}' at byte {} is not Pig Latin: {reason}",
+            self.token, self.offset
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn vowel_and_consonant_words() {
+        assert_eq!(
+            convert_to_pig_latin("first apple cbnm because"),
+            "irst-fay apple-hay cbnm-ay ecause-bay"
+        );
+        assert_eq!(convert_to_pig_latin("string"), "ing-stray");
+    }
+
+    #[test]
+    fn y_and_qu_rules() {
+        assert_eq!(convert_to_pig_latin("yellow"), "ellow-yay");
+        assert_eq!(convert_to_pig_latin("rhythm"), "ythm-rhay");
+        assert_eq!(convert_to_pig_latin("my"), "y-may");
+        assert_eq!(convert_to_pig_latin("queen"), "een-quay");
+        assert_eq!(convert_to_pig_latin("square"), "are-squay");
+        assert_eq!(convert_to_pig_latin("aqua"), "aqua-hay");
+    }
+
+    #[test]
+    fn keeps_punctuation_and_whitespace_in_place() {
+        assert_eq!(convert_to_pig_latin("hello,"), "ello-hay,");
+        assert_eq!(
+            convert_to_pig_latin("  Hello,\tworld!\n(42 apples)"),
+            "  Ello-hay,\torld-way!\n(42 apples-hay)"
+        );
+        assert_eq!(convert_to_pig_latin(""), "");
+    }
+
+    #[test]
+    fn preserves_case() {
+        assert_eq!(convert_to_pig_latin("Hello"), "Ello-hay");
+        assert_eq!(convert_to_pig_latin("Apple"), "Apple-hay");
+        assert_eq!(convert_to_pig_latin("HELLO World"), "ELLO-HAY Orld-way");
+        assert_eq!(convert_to_pig_latin("McDonald"), "onald-McDay");
+        assert_eq!(convert_to_pig_latin("I"), "I-hay");
+    }
+
+    #[test]
+    fn multibyte_text() {
+        assert_eq!(convert_to_pig_latin("école"), "école-hay");
+        assert_eq!(convert_to_pig_latin("Ñandú"), "Andú-ñay");
+        // "e" followed by a combining acute accent is one grapheme and still a vowel.
+        assert_eq!(convert_to_pig_latin("cafe\u{301}"), "afe\u{301}-cay");
+        assert_eq!(convert_to_pig_latin("über straße"), "über-hay aße-stray");
+        assert_eq!(convert_to_pig_latin("東京 tokyo"), "東京 okyo-tay");
+    }
+
+    #[test]
+    fn decodes_back_to_the_original() {
+        let original = "  Hello, world! The QUICK rhythm of école, yellow squares (42 hmm).\n";
+        let encoded = convert_to_pig_latin(original);
+
+        assert_eq!(
+            from_pig_latin(&encoded),
+            Ok(original.replace("Hello", "Ello"))
+        );
+        assert_eq!(from_pig_latin("onald-McDay"), Ok("McDonald".to_string()));
+        assert_eq!(
+            from_pig_latin("ell-way-own-knay"),
+            Ok("well-known".to_string())
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let error = from_pig_latin("irst-fay hello").unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError {
+                token: "hello".to_string(),
+                offset: 9,
+                kind: DecodeErrorKind::MissingSuffix
             }
-        }
+        );
+        assert_eq!(
+            error.to_string(),
+            "'hello' at byte 9 is not Pig Latin: the word has no \"-...ay\" suffix"
+        );
+
+        let kind = |text| from_pig_latin(text).unwrap_err().kind;
+        assert_eq!(kind("ello-hey"), DecodeErrorKind::MissingAy);
+        assert_eq!(kind("tring-say"), DecodeErrorKind::NotEncoderOutput);
+        assert_eq!(kind("apple-ay"), DecodeErrorKind::NotEncoderOutput);
+        assert_eq!(kind("ello-"), DecodeErrorKind::MissingSuffix);
+    }
+
+    // Lowercase, capitalized or all-caps words, minus the "h" + vowel words that
+    // `from_pig_latin` documents as ambiguous.
+    fn word() -> impl Strategy<Value = String> {
+        ("[a-zéèüñø]{1,8}", 0..3)
+            .prop_filter("h + vowel words are ambiguous", |(word, _)| {
+                let graphemes: Vec<&str> = word.graphemes(true).collect();
+                !(graphemes[0] == "h" && split_point(&graphemes) == Some(1))
+            })
+            .prop_map(|(word, case)| match case {
+                0 => word,
+                1 => capitalize(&word),
+                _ => word.to_uppercase(),
+            })
     }
 
-    result.join(" ")
+    fn text() -> impl Strategy<Value = String> {
+        (
+            "[ \n]{0,2}",
+            prop::collection::vec((word(), "[ ,!?;()\n]{1,3}"), 0..8),
+        )
+            .prop_map(|(start, words)| {
+                words
+                    .into_iter()
+                    .fold(start, |mut text, (word, separator)| {
+                        text.push_str(&word);
+                        text.push_str(&separator);
+                        text
+                    })
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn encode_then_decode_is_the_identity(text in text()) {
+            prop_assert_eq!(from_pig_latin(&convert_to_pig_latin(&text)), Ok(text));
+        }
+    }
 }
@@ -1,25 +1,211 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, hash::Hash};
 
-pub fn median(nums: &mut Vec<i32>) -> i32 {
-    nums.sort();
-    nums[(nums.len() - 1) / 2]
+pub mod descriptive;
+pub mod input;
+pub mod streaming;
+
+// All functions here borrow the data as a slice and leave it untouched.
+// When they need sorted data they sort their own copy.
+
+// Lets the same function accept integer and float slices.
+// Every value is widened to f64 for arithmetic; i64 and u64 lose precision beyond 2^53, like `as f64` does.
+pub trait Number: Copy + PartialOrd {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsError {
+    Empty,
+    NotANumber,
+    // `mode` found several values sharing the highest count; `modes` returns all of them.
+    MultipleModes,
+    // Sample variance needs at least two values.
+    NotEnoughValues,
+    // Quantiles must be within 0.0..=1.0 and percentiles within 0.0..=100.0.
+    InvalidQuantile,
+    ZeroBins,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::Empty => write!(f, "no values to compute statistics from"),
+            StatsError::NotANumber => write!(f, "the values contain NaN"),
+            StatsError::MultipleModes => write!(f, "several values share the highest count"),
+            StatsError::NotEnoughValues => write!(f, "at least two values are needed"),
+            StatsError::InvalidQuantile => write!(f, "quantile must be between 0 and 1"),
+            StatsError::ZeroBins => write!(f, "a histogram needs at least one bin"),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
+
+// The median of an even number of values is the mean of the two middle values,
+// so it is returned as an f64 even for integer input.
+pub fn median<T: Number>(nums: &[T]) -> Result<f64, StatsError> {
+    let values: Vec<f64> = nums.iter().map(|n| n.to_f64()).collect();
+    median_f64(&values)
+}
+
+pub fn median_f64(nums: &[f64]) -> Result<f64, StatsError> {
+    if nums.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    if nums.iter().any(|n| n.is_nan()) {
+        return Err(StatsError::NotANumber);
+    }
+
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+
+    let middle = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        Ok(sorted[middle])
+    } else {
+        let (low, high) = (sorted[middle - 1], sorted[middle]);
+        Ok(low + (high - low) / 2.0)
+    }
 }
 
-pub fn mode(nums: &mut Vec<i32>) -> (i32, i32) {
-    let mut result: i32 = nums.get(0).copied().unwrap_or(0);
-    let mut max_count = 0;
+// Every value that occurs most often, in ascending order, together with how often each occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modes<T> {
+    pub values: Vec<T>,
+    pub count: usize,
+}
 
-    let mut map: HashMap<&i32, i32> = HashMap::new();
+pub fn modes<T>(nums: &[T]) -> Result<Modes<T>, StatsError>
+where
+    T: Copy + Ord + Hash,
+{
+    let mut counts: HashMap<T, usize> = HashMap::new();
 
     for num in nums {
-        let count = map.entry(num).or_insert(0);
+        let count = counts.entry(*num).or_insert(0);
         *count += 1;
+    }
+
+    let count = counts.values().copied().max().ok_or(StatsError::Empty)?;
+    let mut values: Vec<T> = counts
+        .into_iter()
+        .filter(|(_, c)| *c == count)
+        .map(|(value, _)| value)
+        .collect();
+    values.sort_unstable();
 
-        if *count >= max_count {
-            max_count = *count;
-            result = *num;
+    Ok(Modes { values, count })
+}
+
+// f64 is neither Ord nor Hash, so equal values are counted as runs in a sorted copy instead.
+pub fn modes_f64(nums: &[f64]) -> Result<Modes<f64>, StatsError> {
+    if nums.iter().any(|n| n.is_nan()) {
+        return Err(StatsError::NotANumber);
+    }
+
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+
+    let mut modes = Modes {
+        values: Vec::new(),
+        count: 0,
+    };
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if run.len() > modes.count {
+            modes.values.clear();
+            modes.count = run.len();
+        }
+        if run.len() == modes.count {
+            modes.values.push(run[0]);
         }
     }
 
-    (result, max_count)
+    if modes.values.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    Ok(modes)
+}
+
+// The single most common value and its count.
+// Ties are reported as an error instead of silently picking one of them.
+pub fn mode<T>(nums: &[T]) -> Result<(T, usize), StatsError>
+where
+    T: Copy + Ord + Hash,
+{
+    let modes = modes(nums)?;
+
+    match modes.values.as_slice() {
+        [value] => Ok((*value, modes.count)),
+        _ => Err(StatsError::MultipleModes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_and_even_lengths() {
+        assert_eq!(median(&[3, 1, 2]), Ok(2.0));
+        assert_eq!(median(&[4, 1, 3, 2]), Ok(2.5));
+        assert_eq!(median(&[i32::MAX, i32::MAX - 1]), Ok(i32::MAX as f64 - 0.5));
+        assert_eq!(median::<i32>(&[]), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn median_does_not_touch_the_input() {
+        let nums = vec![5, 3, 9, 1];
+        median(&nums).unwrap();
+        assert_eq!(nums, [5, 3, 9, 1]);
+    }
+
+    #[test]
+    fn median_of_floats() {
+        assert_eq!(median_f64(&[1.5, -2.0, 10.0]), Ok(1.5));
+        assert_eq!(median_f64(&[1.0, 2.0]), Ok(1.5));
+        assert_eq!(median_f64(&[1.0, f64::NAN]), Err(StatsError::NotANumber));
+        assert_eq!(median(&[1.5_f32, -2.0, 10.0]), Ok(1.5));
+        assert_eq!(median(&[1.0, f64::NAN]), Err(StatsError::NotANumber));
+        assert_eq!(median_f64(&[]), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn mode_and_modes() {
+        assert_eq!(mode(&[3, 4, 8, 2, 2, 5, 2]), Ok((2, 3)));
+        assert_eq!(mode(&[1, 1, 2, 2]), Err(StatsError::MultipleModes));
+        assert_eq!(
+            modes(&[3, 1, 3, 1, 2]),
+            Ok(Modes {
+                values: vec![1, 3],
+                count: 2
+            })
+        );
+        assert_eq!(mode::<i32>(&[]), Err(StatsError::Empty));
+        assert_eq!(modes::<i32>(&[]), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn modes_of_floats() {
+        assert_eq!(
+            modes_f64(&[2.5, 1.0, 2.5, 1.0, 3.0]),
+            Ok(Modes {
+                values: vec![1.0, 2.5],
+                count: 2
+            })
+        );
+        assert_eq!(modes_f64(&[1.0, f64::NAN]), Err(StatsError::NotANumber));
+        assert_eq!(modes_f64(&[]), Err(StatsError::Empty));
+    }
 }
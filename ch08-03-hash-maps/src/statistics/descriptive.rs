@@ -0,0 +1,331 @@
+use std::fmt::{self, Write};
+
+use super::{Number, StatsError, streaming::RunningStats};
+
+// How a quantile that falls between two data points is resolved.
+// The names and behaviour follow numpy's `percentile(method=...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarianceKind {
+    // Divide by n: the data is the whole population.
+    Population,
+    // Divide by n - 1: the data is a sample of a larger population.
+    Sample,
+}
+
+pub fn mean<T: Number>(nums: &[T]) -> Result<f64, StatsError> {
+    RunningStats::from_slice(nums)?.mean()
+}
+
+pub fn variance<T: Number>(nums: &[T], kind: VarianceKind) -> Result<f64, StatsError> {
+    RunningStats::from_slice(nums)?.variance(kind)
+}
+
+pub fn std_dev<T: Number>(nums: &[T], kind: VarianceKind) -> Result<f64, StatsError> {
+    variance(nums, kind).map(f64::sqrt)
+}
+
+pub fn min<T: Number>(nums: &[T]) -> Result<T, StatsError> {
+    extreme(nums, |candidate, current| candidate < current)
+}
+
+pub fn max<T: Number>(nums: &[T]) -> Result<T, StatsError> {
+    extreme(nums, |candidate, current| candidate > current)
+}
+
+// The distance between the smallest and the largest value.
+pub fn range<T: Number>(nums: &[T]) -> Result<f64, StatsError> {
+    Ok(max(nums)?.to_f64() - min(nums)?.to_f64())
+}
+
+fn extreme<T: Number>(nums: &[T], better: impl Fn(T, T) -> bool) -> Result<T, StatsError> {
+    let (first, rest) = nums.split_first().ok_or(StatsError::Empty)?;
+    let mut best = *first;
+
+    for &num in rest {
+        if num.to_f64().is_nan() {
+            return Err(StatsError::NotANumber);
+        }
+        if better(num, best) {
+            best = num;
+        }
+    }
+
+    if best.to_f64().is_nan() {
+        return Err(StatsError::NotANumber);
+    }
+    Ok(best)
+}
+
+// `q` is a fraction between 0.0 and 1.0, so `quantile(nums, 0.5, ..)` is the median.
+pub fn quantile<T: Number>(
+    nums: &[T],
+    q: f64,
+    interpolation: Interpolation,
+) -> Result<f64, StatsError> {
+    let sorted = sorted_f64(nums)?;
+    quantile_sorted(&sorted, q, interpolation)
+}
+
+// Same as `quantile`, with `p` between 0.0 and 100.0.
+pub fn percentile<T: Number>(
+    nums: &[T],
+    p: f64,
+    interpolation: Interpolation,
+) -> Result<f64, StatsError> {
+    quantile(nums, p / 100.0, interpolation)
+}
+
+fn sorted_f64<T: Number>(nums: &[T]) -> Result<Vec<f64>, StatsError> {
+    if nums.is_empty() {
+        return Err(StatsError::Empty);
+    }
+
+    let mut sorted: Vec<f64> = nums.iter().map(|n| n.to_f64()).collect();
+    if sorted.iter().any(|n| n.is_nan()) {
+        return Err(StatsError::NotANumber);
+    }
+    sorted.sort_unstable_by(f64::total_cmp);
+    Ok(sorted)
+}
+
+fn quantile_sorted(
+    sorted: &[f64],
+    q: f64,
+    interpolation: Interpolation,
+) -> Result<f64, StatsError> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(StatsError::InvalidQuantile);
+    }
+
+    let position = q * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let (low, high) = (sorted[below], sorted[above]);
+
+    let value = match interpolation {
+        Interpolation::Linear => low + (high - low) * (position - below as f64),
+        Interpolation::Lower => low,
+        Interpolation::Higher => high,
+        Interpolation::Nearest => sorted[position.round_ties_even() as usize],
+        Interpolation::Midpoint => low + (high - low) / 2.0,
+    };
+
+    Ok(value)
+}
+
+// Equal-width bins from the smallest to the largest value.
+// `edges` has one more entry than `counts`; every bin includes its lower edge,
+// and the last bin also includes the upper edge so the maximum is counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+pub fn histogram<T: Number>(nums: &[T], bins: usize) -> Result<Histogram, StatsError> {
+    if bins == 0 {
+        return Err(StatsError::ZeroBins);
+    }
+    let low = min(nums)?.to_f64();
+    let high = max(nums)?.to_f64();
+    let width = (high - low) / bins as f64;
+
+    let edges = (0..=bins).map(|i| low + width * i as f64).collect();
+    let mut counts = vec![0; bins];
+
+    for num in nums {
+        let bin = if width == 0.0 {
+            0
+        } else {
+            (((num.to_f64() - low) / width) as usize).min(bins - 1)
+        };
+        counts[bin] += 1;
+    }
+
+    Ok(Histogram { edges, counts })
+}
+
+// Everything `stats` usually wants to know about a data set.
+// Variance and standard deviation are the sample versions, which need at least two values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub variance: Option<f64>,
+    pub std_dev: Option<f64>,
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub range: f64,
+}
+
+impl Summary {
+    // Mean, variance, min and max come from a single pass over the data;
+    // the quartiles need one sort of a copy of the data.
+    pub fn from_slice<T: Number>(nums: &[T]) -> Result<Summary, StatsError> {
+        let stats = RunningStats::from_slice(nums)?;
+        let sorted = sorted_f64(nums)?;
+        let (min, max) = (stats.min()?, stats.max()?);
+        let quartile = |q| quantile_sorted(&sorted, q, Interpolation::Linear);
+
+        let variance = stats.variance(VarianceKind::Sample).ok();
+
+        Ok(Summary {
+            count: stats.count(),
+            mean: stats.mean()?,
+            variance,
+            std_dev: variance.map(f64::sqrt),
+            min,
+            q1: quartile(0.25)?,
+            median: quartile(0.5)?,
+            q3: quartile(0.75)?,
+            max,
+            range: max - min,
+        })
+    }
+
+    fn fields(&self) -> [(&'static str, Option<f64>); 10] {
+        [
+            ("count", Some(self.count as f64)),
+            ("mean", Some(self.mean)),
+            ("variance", self.variance),
+            ("std_dev", self.std_dev),
+            ("min", Some(self.min)),
+            ("q1", Some(self.q1)),
+            ("median", Some(self.median)),
+            ("q3", Some(self.q3)),
+            ("max", Some(self.max)),
+            ("range", Some(self.range)),
+        ]
+    }
+
+    // A flat JSON object. Missing and non-finite values become `null`, since JSON has no NaN or infinity.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+
+        for (index, (name, value)) in self.fields().iter().enumerate() {
+            if index > 0 {
+                json.push_str(", ");
+            }
+            match value {
+                Some(value) if value.is_finite() => write!(json, "\"{name}\": {value}"),
+                _ => write!(json, "\"{name}\": null"),
+            }
+            .expect("writing to a String cannot fail");
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+// Prints the summary as a two-column table.
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in self.fields() {
+            match value {
+                Some(value) if name == "count" => writeln!(f, "{name:<10}{value:>14}")?,
+                Some(value) => writeln!(f, "{name:<10}{value:>14.4}")?,
+                None => writeln!(f, "{name:<10}{:>14}", "n/a")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn mean_variance_and_std_dev() {
+        let nums = [2, 4, 4, 4, 5, 5, 7, 9];
+
+        assert_eq!(mean(&nums), Ok(5.0));
+        assert_eq!(variance(&nums, VarianceKind::Population), Ok(4.0));
+        assert_eq!(std_dev(&nums, VarianceKind::Population), Ok(2.0));
+        assert!(close(
+            variance(&nums, VarianceKind::Sample).unwrap(),
+            32.0 / 7.0
+        ));
+        assert_eq!(
+            variance(&[1.0], VarianceKind::Sample),
+            Err(StatsError::NotEnoughValues)
+        );
+        assert_eq!(mean::<f64>(&[]), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn works_for_integers_and_floats() {
+        assert_eq!(min(&[3u8, 1, 2]), Ok(1));
+        assert_eq!(max(&[-1.5f32, 2.5, 0.0]), Ok(2.5));
+        assert_eq!(range(&[10i64, -5, 3]), Ok(15.0));
+        assert_eq!(min(&[1.0, f64::NAN]), Err(StatsError::NotANumber));
+    }
+
+    #[test]
+    fn quantile_interpolations() {
+        let nums = [1, 2, 3, 4];
+
+        assert_eq!(quantile(&nums, 0.5, Interpolation::Linear), Ok(2.5));
+        assert_eq!(quantile(&nums, 0.5, Interpolation::Lower), Ok(2.0));
+        assert_eq!(quantile(&nums, 0.5, Interpolation::Higher), Ok(3.0));
+        assert_eq!(quantile(&nums, 0.5, Interpolation::Midpoint), Ok(2.5));
+        assert_eq!(quantile(&nums, 0.4, Interpolation::Nearest), Ok(2.0));
+        // Halfway between two points, numpy picks the one at the even index.
+        assert_eq!(quantile(&nums, 0.5, Interpolation::Nearest), Ok(3.0));
+        assert_eq!(quantile(&[1, 2], 0.5, Interpolation::Nearest), Ok(1.0));
+        assert_eq!(percentile(&nums, 100.0, Interpolation::Linear), Ok(4.0));
+        assert_eq!(percentile(&nums, 0.0, Interpolation::Linear), Ok(1.0));
+        assert_eq!(
+            quantile(&nums, 1.5, Interpolation::Linear),
+            Err(StatsError::InvalidQuantile)
+        );
+    }
+
+    #[test]
+    fn histogram_bins() {
+        let histogram = histogram(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 10], 5).unwrap();
+
+        assert_eq!(histogram.edges, [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert_eq!(histogram.counts, [2, 2, 2, 2, 2]);
+        assert_eq!(super::histogram(&[3, 3], 4).unwrap().counts, [2, 0, 0, 0]);
+        assert_eq!(super::histogram(&[1], 0), Err(StatsError::ZeroBins));
+    }
+
+    #[test]
+    fn summary_table_and_json() {
+        let summary = Summary::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+
+        assert_eq!(summary.count, 5);
+        assert_eq!(summary.median, 3.0);
+        assert_eq!(summary.q1, 2.0);
+        assert_eq!(summary.q3, 4.0);
+        assert_eq!(summary.range, 4.0);
+        assert!(summary.to_string().contains("median            3.0000"));
+        assert!(
+            summary
+                .to_json()
+                .starts_with("{\"count\": 5, \"mean\": 3, ")
+        );
+
+        let single = Summary::from_slice(&[7.5]).unwrap();
+        assert_eq!(single.variance, None);
+        assert!(single.to_json().contains("\"std_dev\": null"));
+    }
+}
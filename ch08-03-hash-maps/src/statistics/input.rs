@@ -0,0 +1,198 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+// Which field of a CSV line holds the numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    // Counted from 1, like `cut -f`.
+    Index(usize),
+    // Looked up in the header line, which is then skipped.
+    Name(String),
+}
+
+// How the numbers are laid out in the input.
+//
+// Without a column every line may hold any number of values separated by commas and/or
+// whitespace. With a column each line is a CSV record and only that field is read.
+// Blank lines are always ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Format {
+    pub column: Option<Column>,
+    pub skip_header: bool,
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    InvalidNumber { line: usize, token: String },
+    MissingColumn { line: usize, column: usize },
+    UnknownColumn(String),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "could not read input: {error}"),
+            ReadError::InvalidNumber { line, token } => {
+                write!(f, "line {line}: '{token}' is not a number")
+            }
+            ReadError::MissingColumn { line, column } => {
+                write!(f, "line {line}: there is no column {column}")
+            }
+            ReadError::UnknownColumn(name) => {
+                write!(f, "the header has no column named '{name}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+// Reads every number from `reader`, stopping at the first malformed line.
+// NaN is rejected like any other non-number so the statistics never see it.
+pub fn read_numbers<R: BufRead>(reader: R, format: &Format) -> Result<Vec<f64>, ReadError> {
+    let mut nums = Vec::new();
+    let mut index = match format.column {
+        Some(Column::Index(index)) => Some(index),
+        _ => None,
+    };
+    let mut skip_header = format.skip_header || matches!(format.column, Some(Column::Name(_)));
+
+    for (line_index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = line_index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if skip_header {
+            skip_header = false;
+            if let Some(Column::Name(name)) = &format.column {
+                let position = fields(&line).position(|field| field == name);
+                index = Some(position.ok_or_else(|| ReadError::UnknownColumn(name.clone()))? + 1);
+            }
+            continue;
+        }
+
+        match index {
+            Some(column) => {
+                let field = fields(&line)
+                    .nth(column - 1)
+                    .ok_or(ReadError::MissingColumn {
+                        line: line_number,
+                        column,
+                    })?;
+                nums.push(parse_number(field, line_number)?);
+            }
+            None => {
+                let tokens = line
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|token| !token.is_empty());
+                for token in tokens {
+                    nums.push(parse_number(token, line_number)?);
+                }
+            }
+        }
+    }
+
+    Ok(nums)
+}
+
+// Splits a CSV record on commas, trimming spaces and one pair of surrounding quotes.
+// Quoted fields containing commas are not supported; numeric columns never need them.
+fn fields(line: &str) -> impl Iterator<Item = &str> {
+    line.split(',').map(|field| {
+        let field = field.trim();
+        field
+            .strip_prefix('"')
+            .and_then(|field| field.strip_suffix('"'))
+            .unwrap_or(field)
+    })
+}
+
+fn parse_number(token: &str, line: usize) -> Result<f64, ReadError> {
+    match token.parse::<f64>() {
+        Ok(num) if !num.is_nan() => Ok(num),
+        _ => Err(ReadError::InvalidNumber {
+            line,
+            token: token.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(input: &str, format: Format) -> Result<Vec<f64>, ReadError> {
+        read_numbers(input.as_bytes(), &format)
+    }
+
+    #[test]
+    fn mixed_separators() {
+        let nums = read("1 2,3\n\n4.5\t-6\n 7 , 8 \n", Format::default()).unwrap();
+        assert_eq!(nums, [1.0, 2.0, 3.0, 4.5, -6.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_token() {
+        let error = read("1\n2\n3 x4\n", Format::default()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: 'x4' is not a number");
+        assert!(read("NaN", Format::default()).is_err());
+    }
+
+    #[test]
+    fn column_by_index_and_by_name() {
+        let csv = "name,age,score\nann,31,\"9.5\"\nbob, 42 ,7\n";
+
+        let by_index = Format {
+            column: Some(Column::Index(2)),
+            skip_header: true,
+        };
+        assert_eq!(read(csv, by_index).unwrap(), [31.0, 42.0]);
+
+        let by_name = Format {
+            column: Some(Column::Name("score".to_string())),
+            skip_header: false,
+        };
+        assert_eq!(read(csv, by_name).unwrap(), [9.5, 7.0]);
+    }
+
+    #[test]
+    fn column_errors() {
+        let csv = "a,b\n1,2\n3\n";
+
+        let missing = Format {
+            column: Some(Column::Index(2)),
+            skip_header: true,
+        };
+        assert_eq!(
+            read(csv, missing).unwrap_err().to_string(),
+            "line 3: there is no column 2"
+        );
+
+        let unknown = Format {
+            column: Some(Column::Name("c".to_string())),
+            skip_header: false,
+        };
+        assert!(matches!(
+            read(csv, unknown),
+            Err(ReadError::UnknownColumn(name)) if name == "c"
+        ));
+    }
+}
@@ -0,0 +1,683 @@
+// Accumulators that see each value once and never need the whole data set in memory
+// (except StreamingMedian, which is exact and therefore has to keep every value).
+//
+// Every accumulator has a `merge` method, so a long stream can be split across threads
+// and the partial results combined at the end.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::{Number, StatsError, descriptive::VarianceKind};
+
+fn checked<T: Number>(value: T) -> Result<f64, StatsError> {
+    let value = value.to_f64();
+    if value.is_nan() {
+        return Err(StatsError::NotANumber);
+    }
+    Ok(value)
+}
+
+// Running count, mean, variance, min and max using Welford's algorithm,
+// which stays accurate even when the values are large and close together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    // Sum of squared differences from the mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats::new()
+    }
+}
+
+impl RunningStats {
+    pub fn new() -> RunningStats {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn from_slice<T: Number>(nums: &[T]) -> Result<RunningStats, StatsError> {
+        let mut stats = RunningStats::new();
+        for &num in nums {
+            stats.push(num)?;
+        }
+        Ok(stats)
+    }
+
+    // NaN is rejected and leaves the accumulator unchanged.
+    pub fn push<T: Number>(&mut self, value: T) -> Result<(), StatsError> {
+        let x = checked(value)?;
+
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        Ok(())
+    }
+
+    // Chan et al.'s formula for combining the moments of two disjoint parts of a data set.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * (self.count * other.count) as f64 / count as f64;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> Result<f64, StatsError> {
+        self.non_empty(self.mean)
+    }
+
+    pub fn variance(&self, kind: VarianceKind) -> Result<f64, StatsError> {
+        match kind {
+            _ if self.count == 0 => Err(StatsError::Empty),
+            VarianceKind::Population => Ok(self.m2 / self.count as f64),
+            VarianceKind::Sample if self.count < 2 => Err(StatsError::NotEnoughValues),
+            VarianceKind::Sample => Ok(self.m2 / (self.count - 1) as f64),
+        }
+    }
+
+    pub fn std_dev(&self, kind: VarianceKind) -> Result<f64, StatsError> {
+        self.variance(kind).map(f64::sqrt)
+    }
+
+    pub fn min(&self) -> Result<f64, StatsError> {
+        self.non_empty(self.min)
+    }
+
+    pub fn max(&self) -> Result<f64, StatsError> {
+        self.non_empty(self.max)
+    }
+
+    fn non_empty(&self, value: f64) -> Result<f64, StatsError> {
+        if self.count == 0 {
+            return Err(StatsError::Empty);
+        }
+        Ok(value)
+    }
+}
+
+// f64 is not Ord because of NaN; the accumulators reject NaN, so total_cmp gives the usual order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Ordered(f64);
+
+impl Eq for Ordered {}
+
+impl PartialOrd for Ordered {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ordered {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Exact running median using two heaps: a max-heap with the lower half of the values
+// and a min-heap with the upper half. The lower half holds at most one extra value.
+//
+// Each push is O(log n) and reading the median is O(1), but all values are kept.
+// Use TDigest when the stream is too large for that.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingMedian {
+    lower: BinaryHeap<Ordered>,
+    upper: BinaryHeap<Reverse<Ordered>>,
+}
+
+impl StreamingMedian {
+    pub fn new() -> StreamingMedian {
+        StreamingMedian::default()
+    }
+
+    pub fn push<T: Number>(&mut self, value: T) -> Result<(), StatsError> {
+        let value = Ordered(checked(value)?);
+
+        match self.lower.peek() {
+            Some(top) if value > *top => self.upper.push(Reverse(value)),
+            _ => self.lower.push(value),
+        }
+
+        if self.lower.len() > self.upper.len() + 1 {
+            let moved = self.lower.pop().expect("lower half is not empty");
+            self.upper.push(Reverse(moved));
+        } else if self.upper.len() > self.lower.len() {
+            let Reverse(moved) = self.upper.pop().expect("upper half is not empty");
+            self.lower.push(moved);
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &StreamingMedian) {
+        let values = other
+            .lower
+            .iter()
+            .chain(other.upper.iter().map(|Reverse(value)| value));
+
+        for value in values {
+            self.push(value.0)
+                .expect("values were checked when first pushed");
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    pub fn median(&self) -> Result<f64, StatsError> {
+        let low = self.lower.peek().ok_or(StatsError::Empty)?.0;
+
+        match self.upper.peek() {
+            Some(Reverse(high)) if self.lower.len() == self.upper.len() => {
+                Ok(low + (high.0 - low) / 2.0)
+            }
+            _ => Ok(low),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+// Approximate quantiles in bounded memory using a merging t-digest (Dunning & Ertl).
+//
+// Values are summarised as weighted centroids. Centroids near the median may absorb many
+// values while those in the tails stay small, so extreme quantiles are the most accurate.
+// A higher compression keeps more centroids: more memory, better accuracy.
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    // Values pushed since the last compression, as centroids of weight 1.
+    buffer: Vec<Centroid>,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        TDigest::new(TDigest::DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+    pub fn new(compression: f64) -> TDigest {
+        assert!(compression >= 1.0, "TDigest compression must be at least 1");
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn push<T: Number>(&mut self, value: T) -> Result<(), StatsError> {
+        let value = checked(value)?;
+
+        self.buffer.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &TDigest) {
+        self.buffer.extend(&other.centroids);
+        self.buffer.extend(&other.buffer);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    pub fn count(&self) -> usize {
+        self.centroids
+            .iter()
+            .chain(&self.buffer)
+            .map(|centroid| centroid.weight)
+            .sum::<f64>() as usize
+    }
+
+    // `q` is a fraction between 0.0 and 1.0, like `descriptive::quantile`.
+    pub fn quantile(&self, q: f64) -> Result<f64, StatsError> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(StatsError::InvalidQuantile);
+        }
+        let centroids = self.compressed();
+        if centroids.is_empty() {
+            return Err(StatsError::Empty);
+        }
+
+        // Each centroid is treated as sitting at the middle of the ranks it covers;
+        // between those points, and out to min and max, the value is interpolated linearly.
+        let total: f64 = centroids.iter().map(|centroid| centroid.weight).sum();
+        let target = q * total;
+        let mut previous = (0.0, self.min);
+        let mut before = 0.0;
+
+        for centroid in &centroids {
+            let center = before + centroid.weight / 2.0;
+            if target < center {
+                return Ok(interpolate(previous, (center, centroid.mean), target));
+            }
+            previous = (center, centroid.mean);
+            before += centroid.weight;
+        }
+
+        Ok(interpolate(previous, (total, self.max), target))
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        (self.compression * 5.0) as usize
+    }
+
+    fn compress(&mut self) {
+        self.centroids = self.compressed();
+        self.buffer.clear();
+    }
+
+    // Sorts all centroids and greedily merges neighbours while the merged weight stays
+    // within the size limit for its position: 4 * total * q * (1 - q) / compression.
+    fn compressed(&self) -> Vec<Centroid> {
+        let mut all: Vec<Centroid> = self.centroids.iter().chain(&self.buffer).copied().collect();
+        all.sort_unstable_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total: f64 = all.iter().map(|centroid| centroid.weight).sum();
+        let mut merged = Vec::new();
+        let mut all = all.into_iter();
+        let Some(mut current) = all.next() else {
+            return merged;
+        };
+        let mut before = 0.0;
+
+        for next in all {
+            let weight = current.weight + next.weight;
+            let q = (before + weight / 2.0) / total;
+
+            if weight <= 4.0 * total * q * (1.0 - q) / self.compression {
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                current = next;
+            }
+        }
+
+        merged.push(current);
+        merged
+    }
+}
+
+fn interpolate((x0, y0): (f64, f64), (x1, y1): (f64, f64), x: f64) -> f64 {
+    if x1 <= x0 {
+        return y1;
+    }
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+// One of the most frequent values seen by SpaceSaving.
+// The true count is between `count - error` and `count`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frequent<T> {
+    pub value: T,
+    pub count: u64,
+    pub error: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Counter {
+    count: u64,
+    error: u64,
+}
+
+// Approximate top-k frequent values with the Space-Saving algorithm (Metwally et al.).
+//
+// At most `capacity` values are tracked. When a new value arrives and every slot is taken,
+// it replaces the value with the lowest count and inherits that count as its error.
+// Any value that occurs more than total / capacity times is guaranteed to be tracked.
+#[derive(Debug, Clone)]
+pub struct SpaceSaving<T> {
+    capacity: usize,
+    counters: HashMap<T, Counter>,
+    total: u64,
+}
+
+impl<T> SpaceSaving<T>
+where
+    T: Clone + Eq + Hash + Ord,
+{
+    pub fn new(capacity: usize) -> SpaceSaving<T> {
+        assert!(capacity > 0, "SpaceSaving capacity must be at least 1");
+        SpaceSaving {
+            capacity,
+            counters: HashMap::with_capacity(capacity),
+            total: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.total += 1;
+
+        if let Some(counter) = self.counters.get_mut(&value) {
+            counter.count += 1;
+            return;
+        }
+        if self.counters.len() < self.capacity {
+            self.counters.insert(value, Counter { count: 1, error: 0 });
+            return;
+        }
+
+        let (evicted, floor) = self
+            .counters
+            .iter()
+            .min_by(|(a, x), (b, y)| x.count.cmp(&y.count).then_with(|| b.cmp(a)))
+            .map(|(value, counter)| (value.clone(), counter.count))
+            .expect("capacity is at least 1");
+        self.counters.remove(&evicted);
+        self.counters.insert(
+            value,
+            Counter {
+                count: floor + 1,
+                error: floor,
+            },
+        );
+    }
+
+    // Merges two summaries as described by Agarwal et al. ("Mergeable Summaries"):
+    // a value missing from one summary is assumed to have that summary's lowest count.
+    pub fn merge(&mut self, other: &SpaceSaving<T>) {
+        let own_floor = self.floor();
+        let other_floor = other.floor();
+        let assumed = |floor| Counter {
+            count: floor,
+            error: floor,
+        };
+
+        let mut combined: HashMap<T, Counter> = HashMap::new();
+        for value in self.counters.keys().chain(other.counters.keys()) {
+            if combined.contains_key(value) {
+                continue;
+            }
+            let own = self
+                .counters
+                .get(value)
+                .copied()
+                .unwrap_or(assumed(own_floor));
+            let theirs = other
+                .counters
+                .get(value)
+                .copied()
+                .unwrap_or(assumed(other_floor));
+            combined.insert(
+                value.clone(),
+                Counter {
+                    count: own.count + theirs.count,
+                    error: own.error + theirs.error,
+                },
+            );
+        }
+
+        let mut combined: Vec<(T, Counter)> = combined.into_iter().collect();
+        combined.sort_unstable_by(|(a, x), (b, y)| y.count.cmp(&x.count).then_with(|| a.cmp(b)));
+        combined.truncate(self.capacity);
+
+        self.counters = combined.into_iter().collect();
+        self.total += other.total;
+    }
+
+    // How many values have been pushed, including those no longer tracked.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    // The `k` values with the highest counts, most frequent first. Ties are ordered by value.
+    pub fn top(&self, k: usize) -> Vec<Frequent<T>> {
+        let mut top: Vec<Frequent<T>> = self
+            .counters
+            .iter()
+            .map(|(value, counter)| Frequent {
+                value: value.clone(),
+                count: counter.count,
+                error: counter.error,
+            })
+            .collect();
+
+        top.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        top.truncate(k);
+        top
+    }
+
+    // The lowest count among tracked values once every slot is taken; any untracked
+    // value can have occurred at most this many times.
+    fn floor(&self) -> u64 {
+        if self.counters.len() < self.capacity {
+            return 0;
+        }
+        self.counters
+            .values()
+            .map(|counter| counter.count)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::descriptive;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn running_stats_match_the_slice_functions() {
+        let nums = [2.5, -1.0, 4.0, 4.0, 10.0, 3.25];
+        let stats = RunningStats::from_slice(&nums).unwrap();
+
+        assert_eq!(stats.count(), 6);
+        assert!(close(
+            stats.mean().unwrap(),
+            descriptive::mean(&nums).unwrap()
+        ));
+        assert!(close(
+            stats.variance(VarianceKind::Sample).unwrap(),
+            descriptive::variance(&nums, VarianceKind::Sample).unwrap()
+        ));
+        assert_eq!(stats.min(), Ok(-1.0));
+        assert_eq!(stats.max(), Ok(10.0));
+        assert_eq!(RunningStats::new().mean(), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn running_stats_merge_equals_one_pass() {
+        let nums: Vec<i32> = (1..=100).map(|n| n * n % 37).collect();
+        let (left, right) = nums.split_at(41);
+
+        let mut merged = RunningStats::from_slice(left).unwrap();
+        merged.merge(&RunningStats::from_slice(right).unwrap());
+        merged.merge(&RunningStats::new());
+        let whole = RunningStats::from_slice(&nums).unwrap();
+
+        assert_eq!(merged.count(), whole.count());
+        assert!(close(merged.mean().unwrap(), whole.mean().unwrap()));
+        assert!(close(
+            merged.variance(VarianceKind::Population).unwrap(),
+            whole.variance(VarianceKind::Population).unwrap()
+        ));
+        assert_eq!(merged.min(), whole.min());
+        assert_eq!(merged.max(), whole.max());
+    }
+
+    #[test]
+    fn streaming_median_tracks_every_prefix() {
+        let nums = [5, 1, 9, 3, 3, 8, -2, 7];
+        let mut median = StreamingMedian::new();
+
+        for (i, &num) in nums.iter().enumerate() {
+            median.push(num).unwrap();
+            assert_eq!(median.median(), super::super::median(&nums[..=i]));
+        }
+        assert_eq!(StreamingMedian::new().median(), Err(StatsError::Empty));
+        assert_eq!(median.push(f64::NAN), Err(StatsError::NotANumber));
+    }
+
+    #[test]
+    fn streaming_median_merge() {
+        let mut left = StreamingMedian::new();
+        let mut right = StreamingMedian::new();
+        for n in 0..10 {
+            left.push(n).unwrap();
+            right.push(n + 100).unwrap();
+        }
+
+        left.merge(&right);
+        assert_eq!(left.count(), 20);
+        assert_eq!(left.median(), Ok(54.5));
+    }
+
+    #[test]
+    fn tdigest_quantiles_are_close() {
+        let mut digest = TDigest::default();
+        for n in 1..=10_000 {
+            digest.push(n).unwrap();
+        }
+
+        assert_eq!(digest.count(), 10_000);
+        assert_eq!(digest.quantile(0.0), Ok(1.0));
+        assert_eq!(digest.quantile(1.0), Ok(10_000.0));
+        for q in [0.01, 0.25, 0.5, 0.75, 0.99] {
+            let expected = q * 10_000.0;
+            let estimate = digest.quantile(q).unwrap();
+            assert!(
+                (estimate - expected).abs() < 50.0,
+                "q = {q}: {estimate} vs {expected}"
+            );
+        }
+        assert_eq!(digest.quantile(2.0), Err(StatsError::InvalidQuantile));
+        assert_eq!(TDigest::default().quantile(0.5), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn tdigest_small_inputs_are_exact() {
+        let mut digest = TDigest::default();
+        for n in [4, 1, 3, 2] {
+            digest.push(n).unwrap();
+        }
+        assert_eq!(digest.quantile(0.5), Ok(2.5));
+    }
+
+    #[test]
+    fn tdigest_merge() {
+        let mut parts: Vec<TDigest> = (0..4).map(|_| TDigest::default()).collect();
+        for n in 0..20_000 {
+            parts[n % 4].push(n as f64).unwrap();
+        }
+
+        let mut merged = TDigest::default();
+        for part in &parts {
+            merged.merge(part);
+        }
+
+        assert_eq!(merged.count(), 20_000);
+        assert!((merged.quantile(0.5).unwrap() - 10_000.0).abs() < 100.0);
+        assert!((merged.quantile(0.9).unwrap() - 18_000.0).abs() < 100.0);
+    }
+
+    #[test]
+    fn space_saving_finds_heavy_hitters() {
+        let mut top = SpaceSaving::new(3);
+        for value in [1, 2, 1, 3, 1, 4, 1, 5, 2, 1] {
+            top.push(value);
+        }
+
+        let frequent = top.top(1);
+        assert_eq!(frequent[0].value, 1);
+        assert!(frequent[0].count - frequent[0].error <= 5 && 5 <= frequent[0].count);
+        assert_eq!(top.total(), 10);
+        assert_eq!(top.top(10).len(), 3);
+    }
+
+    #[test]
+    fn space_saving_exact_when_under_capacity() {
+        let mut top = SpaceSaving::new(10);
+        for word in "the cat and the hat and the bat".split_whitespace() {
+            top.push(word);
+        }
+
+        assert_eq!(
+            top.top(2),
+            [
+                Frequent {
+                    value: "the",
+                    count: 3,
+                    error: 0
+                },
+                Frequent {
+                    value: "and",
+                    count: 2,
+                    error: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn space_saving_merge() {
+        let mut left = SpaceSaving::new(4);
+        let mut right = SpaceSaving::new(4);
+        for n in 0..200 {
+            left.push(if n % 2 == 0 { 7 } else { n + 1000 });
+            right.push(if n % 3 == 0 { 7 } else { n + 2000 });
+        }
+
+        left.merge(&right);
+        let top = left.top(1);
+        assert_eq!(left.total(), 400);
+        assert_eq!(top[0].value, 7);
+        assert!(top[0].count - top[0].error <= 167 && 167 <= top[0].count);
+    }
+}
@@ -0,0 +1,127 @@
+use unicode_segmentation::{UWordBounds, UnicodeSegmentation};
+
+// A piece of text between two Unicode word boundaries (UAX #29).
+// Concatenating the tokens of a text always gives back the text unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    // Contains at least one letter or digit: "hello", "don't", "42", "東".
+    Word(&'a str),
+    // A run of whitespace, including newlines.
+    Whitespace(&'a str),
+    // Anything else, usually a single punctuation mark or symbol.
+    Punctuation(&'a str),
+}
+
+impl<'a> Token<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Token::Word(text) | Token::Whitespace(text) | Token::Punctuation(text) => text,
+        }
+    }
+}
+
+pub struct Tokens<'a> {
+    bounds: UWordBounds<'a>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let text = self.bounds.next()?;
+
+        let token = if text.chars().any(char::is_alphanumeric) {
+            Token::Word(text)
+        } else if text.chars().all(char::is_whitespace) {
+            Token::Whitespace(text)
+        } else {
+            Token::Punctuation(text)
+        };
+        Some(token)
+    }
+}
+
+pub fn tokenize(text: &str) -> Tokens<'_> {
+    Tokens {
+        bounds: text.split_word_bounds(),
+    }
+}
+
+// How a word is capitalized, so a transform can rebuild the same capitalization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Case {
+    // First letter uppercase, every other letter lowercase: "Hello", "I".
+    Title,
+    // At least two letters, all uppercase: "HELLO".
+    Upper,
+    Other,
+}
+
+impl Case {
+    pub(crate) fn of(word: &str) -> Case {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let Some(first) = letters.next() else {
+            return Case::Other;
+        };
+        let rest: Vec<char> = letters.collect();
+
+        if !first.is_uppercase() {
+            Case::Other
+        } else if rest.iter().all(|c| !c.is_uppercase()) {
+            Case::Title
+        } else if rest.iter().all(|c| !c.is_lowercase()) {
+            Case::Upper
+        } else {
+            Case::Other
+        }
+    }
+}
+
+pub(crate) fn capitalize(word: &str) -> String {
+    map_first_grapheme(word, str::to_uppercase)
+}
+
+pub(crate) fn decapitalize(word: &str) -> String {
+    map_first_grapheme(word, str::to_lowercase)
+}
+
+fn map_first_grapheme(word: &str, map: impl Fn(&str) -> String) -> String {
+    match word.graphemes(true).next() {
+        Some(first) => map(first) + &word[first.len()..],
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_words_punctuation_and_whitespace() {
+        let tokens: Vec<Token> = tokenize("Don't panic,  it's 42!\n").collect();
+
+        assert_eq!(
+            tokens,
+            [
+                Token::Word("Don't"),
+                Token::Whitespace(" "),
+                Token::Word("panic"),
+                Token::Punctuation(","),
+                Token::Whitespace("  "),
+                Token::Word("it's"),
+                Token::Whitespace(" "),
+                Token::Word("42"),
+                Token::Punctuation("!"),
+                Token::Whitespace("\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens_concatenate_to_the_input() {
+        let text = "Ça va? — très bien,\tmerci…\r\n東京 🙂";
+        let joined: String = tokenize(text).map(|token| token.as_str()).collect();
+
+        assert_eq!(joined, text);
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    pig_latin::{self, VOWELS, base_letter},
+    tokenizer::{Case, Token, capitalize, decapitalize, tokenize},
+};
+
+// A word game that rewrites each word on its own.
+//
+// Only `transform_word` needs implementing: `transform` runs it over every word the tokenizer
+// finds and copies punctuation and whitespace through unchanged, so every game preserves
+// the layout of the text the same way.
+pub trait WordTransform {
+    fn name(&self) -> &'static str;
+
+    fn transform_word(&self, word: &str) -> String;
+
+    fn transform(&self, text: &str) -> String {
+        tokenize(text)
+            .map(|token| match token {
+                Token::Word(word) => self.transform_word(word),
+                other => other.as_str().to_string(),
+            })
+            .collect()
+    }
+}
+
+pub const TRANSFORM_NAMES: [&str; 4] = ["pig-latin", "ubbi-dubbi", "rot13", "reverse"];
+
+pub fn by_name(name: &str) -> Option<Box<dyn WordTransform>> {
+    match name {
+        "pig-latin" => Some(Box::new(PigLatin)),
+        "ubbi-dubbi" => Some(Box::new(UbbiDubbi)),
+        "rot13" => Some(Box::new(Rot13)),
+        "reverse" => Some(Box::new(Reverse)),
+        _ => None,
+    }
+}
+
+// "Hello" becomes "Ello-hay"; see `pig_latin::word_to_pig_latin` for the rules.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PigLatin;
+
+impl WordTransform for PigLatin {
+    fn name(&self) -> &'static str {
+        "pig-latin"
+    }
+
+    fn transform_word(&self, word: &str) -> String {
+        pig_latin::word_to_pig_latin(word)
+    }
+}
+
+// Puts "ub" before every vowel sound: "hello" becomes "hubellubo".
+//
+// A run of vowels is one sound ("boat" becomes "buboat"), "y" after the first letter is a vowel
+// ("my" becomes "muby"), and a silent "e" at the end of a word is skipped ("cake" becomes "cubake").
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UbbiDubbi;
+
+impl UbbiDubbi {
+    fn convert(word: &str) -> String {
+        let graphemes: Vec<&str> = word.graphemes(true).collect();
+        let letters: Vec<char> = graphemes.iter().map(|g| base_letter(g)).collect();
+        let is_vowel = |index: usize| match letters[index] {
+            'y' => index > 0,
+            letter => VOWELS.contains(letter),
+        };
+
+        let last = letters.len() - 1;
+        let silent_e = letters.len() > 2
+            && letters[last] == 'e'
+            && !is_vowel(last - 1)
+            && (0..last - 1).any(is_vowel);
+
+        let mut converted = String::new();
+        for (index, grapheme) in graphemes.iter().enumerate() {
+            let starts_sound = is_vowel(index) && (index == 0 || !is_vowel(index - 1));
+            if starts_sound && !(silent_e && index == last) {
+                converted.push_str("ub");
+            }
+            converted.push_str(grapheme);
+        }
+        converted
+    }
+}
+
+impl WordTransform for UbbiDubbi {
+    fn name(&self) -> &'static str {
+        "ubbi-dubbi"
+    }
+
+    fn transform_word(&self, word: &str) -> String {
+        if !pig_latin::is_word(word) {
+            return word.to_string();
+        }
+
+        match Case::of(word) {
+            Case::Title => capitalize(&UbbiDubbi::convert(&decapitalize(word))),
+            Case::Upper => UbbiDubbi::convert(&word.to_lowercase()).to_uppercase(),
+            Case::Other => UbbiDubbi::convert(word),
+        }
+    }
+}
+
+// Rotates ASCII letters by 13 places. Applying it twice gives back the original text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rot13;
+
+impl WordTransform for Rot13 {
+    fn name(&self) -> &'static str {
+        "rot13"
+    }
+
+    fn transform_word(&self, word: &str) -> String {
+        word.chars()
+            .map(|c| match c {
+                'a'..='z' => (b'a' + (c as u8 - b'a' + 13) % 26) as char,
+                'A'..='Z' => (b'A' + (c as u8 - b'A' + 13) % 26) as char,
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+// Spells each word backwards by grapheme, so accents stay on their letters.
+// A capitalized word stays capitalized: "Hello" becomes "Olleh".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reverse;
+
+impl WordTransform for Reverse {
+    fn name(&self) -> &'static str {
+        "reverse"
+    }
+
+    fn transform_word(&self, word: &str) -> String {
+        let reverse = |word: &str| word.graphemes(true).rev().collect::<String>();
+
+        match Case::of(word) {
+            Case::Title => capitalize(&reverse(&decapitalize(word))),
+            _ => reverse(word),
+        }
+    }
+}
+
+// Several transforms applied one after the other, each to the whole output of the previous one.
+// `pig-latin,rot13` first converts to Pig Latin, then rotates every word of the result.
+pub struct Chain {
+    transforms: Vec<Box<dyn WordTransform>>,
+}
+
+impl Chain {
+    pub fn new(transforms: Vec<Box<dyn WordTransform>>) -> Chain {
+        Chain { transforms }
+    }
+
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Chain, UnknownTransform> {
+        let transforms = names
+            .iter()
+            .map(|name| {
+                by_name(name.as_ref()).ok_or_else(|| UnknownTransform(name.as_ref().to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Chain::new(transforms))
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.transforms
+            .iter()
+            .map(|transform| transform.name())
+            .collect()
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.transforms
+            .iter()
+            .fold(text.to_string(), |text, transform| {
+                transform.transform(&text)
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTransform(pub String);
+
+impl fmt::Display for UnknownTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown transform '{}', expected one of: {}",
+            self.0,
+            TRANSFORM_NAMES.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownTransform {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_name_resolves() {
+        for name in TRANSFORM_NAMES {
+            assert_eq!(by_name(name).unwrap().name(), name);
+        }
+        assert!(by_name("klingon").is_none());
+    }
+
+    #[test]
+    fn pig_latin_matches_the_module() {
+        let text = "Hello, world! 42 rhythms.";
+        assert_eq!(
+            PigLatin.transform(text),
+            pig_latin::convert_to_pig_latin(text)
+        );
+    }
+
+    #[test]
+    fn ubbi_dubbi() {
+        assert_eq!(
+            UbbiDubbi.transform("Hello, my boat! Apple cake THE 42"),
+            "Hubellubo, muby buboat! Ubapple cubake THUBE 42"
+        );
+    }
+
+    #[test]
+    fn rot13_round_trips() {
+        let text = "Why did the chicken cross the road? Über-fun!";
+        let rotated = Rot13.transform(text);
+
+        assert_eq!(rotated, "Jul qvq gur puvpxra pebff gur ebnq? Üore-sha!");
+        assert_eq!(Rot13.transform(&rotated), text);
+    }
+
+    #[test]
+    fn reverse_keeps_capitals_and_accents() {
+        assert_eq!(
+            Reverse.transform("Hello, cafe\u{301} NASA!"),
+            "Olleh, e\u{301}fac ASAN!"
+        );
+    }
+
+    #[test]
+    fn chains_apply_in_order() {
+        let chain = Chain::from_names(&["pig-latin", "rot13"]).unwrap();
+
+        assert_eq!(chain.names(), ["pig-latin", "rot13"]);
+        assert_eq!(chain.apply("Hello, world!"), "Ryyb-unl, beyq-jnl!");
+        assert_eq!(
+            Chain::from_names(&["reverse", "nope"]).err(),
+            Some(UnknownTransform("nope".to_string()))
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    process::{Command, Output, Stdio},
+};
+
+fn stats(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_stats"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("stats binary starts");
+
+    // On a usage error the binary exits without reading its input, which closes the pipe.
+    let written = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    if let Err(error) = written
+        && error.kind() != ErrorKind::BrokenPipe
+    {
+        panic!("writing to stats: {error}");
+    }
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn reports_selected_statistics_from_stdin() {
+    let output = stats(
+        &["--stats", "count,median,mode,p90", "--json"],
+        "1 2 2\n3,4\n\n10\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"count\": 6, \"median\": 2.5, \"mode\": [2], \"p90\": 7}\n"
+    );
+}
+
+#[test]
+fn reads_a_csv_column_from_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    fs::write(&path, "name,score\nann,4\nbob,8\n").unwrap();
+
+    let output = stats(&["--column", "score", path.to_str().unwrap()], "");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("count                  2\n"));
+    assert!(stdout.contains("mean              6.0000\n"));
+}
+
+#[test]
+fn malformed_line_is_reported_with_its_number() {
+    let output = stats(&[], "1\n2\nthree\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Error: stdin: line 3: 'three' is not a number\n"
+    );
+}
+
+#[test]
+fn unknown_statistic_is_a_usage_error() {
+    let output = stats(&["--stats", "mean,average"], "1\n");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("Error: unknown statistic 'average'")
+    );
+}
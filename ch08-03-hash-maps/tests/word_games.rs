@@ -0,0 +1,45 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn word_games(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_word-games"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("word-games binary starts");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn streams_stdin_through_the_chain() {
+    let output = word_games(&["pig-latin,rot13", "rot13"], "Hello, world!\n\n  The end.");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Ello-hay, orld-way!\n\n  E-thay end-hay."
+    );
+}
+
+#[test]
+fn unknown_transform_is_a_usage_error() {
+    let output = word_games(&["reverse,klingon"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("Error: unknown transform 'klingon'")
+    );
+}
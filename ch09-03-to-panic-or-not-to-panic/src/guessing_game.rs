@@ -1,20 +1,95 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+// The bounds a Guess is validated against.
+// GuessRange::DEFAULT is the 1..=100 range that Guess::new has always used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessRange {
+    min: i32,
+    max: i32,
+}
+
+impl GuessRange {
+    pub const DEFAULT: GuessRange = GuessRange::new(1, 100);
+
+    // Ranges are written into the program, never typed in by the player, so an empty one can
+    // only be fixed by changing the code: this panics instead of returning a Result (see
+    // "Guidelines for error handling" in main.rs).
+    pub const fn new(min: i32, max: i32) -> GuessRange {
+        assert!(min <= max, "GuessRange min must not exceed max");
+        GuessRange { min, max }
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    pub fn try_guess(&self, value: i32) -> Result<Guess, GuessError> {
+        if !self.contains(value) {
+            return Err(GuessError {
+                value,
+                range: *self,
+            });
+        }
+
+        Ok(Guess {
+            value,
+            range: *self,
+        })
+    }
+
+    // Parses user input straight into a validated Guess for this range.
+    pub fn parse_guess(&self, input: &str) -> Result<Guess, ParseGuessError> {
+        let value: i32 = input.trim().parse().map_err(ParseGuessError::Parse)?;
+        self.try_guess(value).map_err(ParseGuessError::Range)
+    }
+}
+
+impl Default for GuessRange {
+    fn default() -> Self {
+        GuessRange::DEFAULT
+    }
+}
+
+impl fmt::Display for GuessRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+// A Guess remembers the range it was checked against, so -5 from -50..=0 is not mistaken
+// for a guess that passed the 1..=100 check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Guess {
     value: i32,
+    range: GuessRange,
 }
 
 impl Guess {
-    // Code outside the guessing_game module must use the Guess::new function to create an instance of Guess,
-    // thereby ensuring that there’s no way for a Guess to have a value that hasn’t been checked by the conditions in the Guess::new function.
+    // Code outside the guessing_game module must use Guess::new, or GuessRange::try_guess for another range, to create an instance of Guess,
+    // thereby ensuring that there’s no way for a Guess to have a value that hasn’t been checked against its range.
 
     /**
-     * Value should be between 1 and 100
+     * Value should be between 1 and 100, the range of GuessRange::DEFAULT
      */
     pub fn new(value: i32) -> Guess {
-        if value < 1 || value > 100 {
-            panic!("Guess value must be between 1 and 100. Got : {value}");
+        match Guess::try_new(value) {
+            Ok(guess) => guess,
+            Err(error) => panic!("{error}"),
         }
+    }
 
-        Guess { value }
+    // The recoverable version of Guess::new: an out-of-range value comes back as an Err
+    // carrying the value and the bounds, so the caller decides whether that is fatal.
+    pub fn try_new(value: i32) -> Result<Guess, GuessError> {
+        GuessRange::DEFAULT.try_guess(value)
     }
 
     // This kind of method is sometimes called a getter because its purpose is to get some data from its fields and return it.
@@ -23,4 +98,123 @@ impl Guess {
     pub fn value(&self) -> i32 {
         self.value
     }
+
+    pub fn range(&self) -> GuessRange {
+        self.range
+    }
+}
+
+impl TryFrom<i32> for Guess {
+    type Error = GuessError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Guess::try_new(value)
+    }
+}
+
+impl FromStr for Guess {
+    type Err = ParseGuessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GuessRange::DEFAULT.parse_guess(s)
+    }
+}
+
+// A value that was a valid number but fell outside the allowed range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessError {
+    pub value: i32,
+    pub range: GuessRange,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Guess value must be between {} and {}. Got : {}",
+            self.range.min, self.range.max, self.value
+        )
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+// Parsing text can fail in two different ways, and callers often want to tell them apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGuessError {
+    Parse(ParseIntError),
+    Range(GuessError),
+}
+
+impl fmt::Display for ParseGuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGuessError::Parse(error) => write!(f, "Guess is not a number: {error}"),
+            ParseGuessError::Range(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseGuessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseGuessError::Parse(error) => Some(error),
+            ParseGuessError::Range(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_new_accepts_bounds_and_rejects_outside() {
+        assert_eq!(Guess::try_new(1).unwrap().value(), 1);
+        assert_eq!(Guess::try_new(100).unwrap().value(), 100);
+        assert_eq!(
+            Guess::try_new(101).err(),
+            Some(GuessError {
+                value: 101,
+                range: GuessRange::DEFAULT
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Guess value must be between 1 and 100. Got : 0")]
+    fn new_still_panics() {
+        Guess::new(0);
+    }
+
+    #[test]
+    fn custom_ranges() {
+        let range = GuessRange::new(-10, 10);
+
+        assert_eq!(range.try_guess(-10).unwrap().value(), -10);
+        assert_eq!(range.try_guess(11).unwrap_err().range, range);
+        assert_eq!(range.parse_guess(" -3\n").unwrap().value(), -3);
+        assert_eq!(range.try_guess(5).unwrap().range(), range);
+        assert_ne!(range.try_guess(5), Guess::try_new(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "min must not exceed max")]
+    fn empty_range_panics() {
+        GuessRange::new(5, 1);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Guess::try_from(42).unwrap().value(), 42);
+        assert_eq!("42".parse::<Guess>().unwrap().value(), 42);
+        assert!(matches!(
+            "forty two".parse::<Guess>(),
+            Err(ParseGuessError::Parse(_))
+        ));
+        assert!(matches!(
+            "420".parse::<Guess>(),
+            Err(ParseGuessError::Range(GuessError { value: 420, .. }))
+        ));
+    }
 }
@@ -0,0 +1,135 @@
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead, Write},
+};
+
+use crate::guessing_game::{Guess, GuessRange, ParseGuessError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won { attempts: u32 },
+    // The input ran out before the secret was found.
+    Quit { attempts: u32 },
+}
+
+// The guessing game loop from chapter 2, rebuilt on the validated Guess type.
+//
+// Invalid input never panics: it is reported as either a parse error or a range error
+// and the player is asked again. Only valid guesses count as attempts.
+//
+// Taking any BufRead/Write pair instead of stdin/stdout lets tests drive the game with in-memory buffers.
+//
+// A secret outside `range` could never be guessed, so that panics before the game starts.
+pub fn play<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    range: GuessRange,
+    secret: Guess,
+) -> io::Result<Outcome> {
+    assert!(
+        range.contains(secret.value()),
+        "the secret {} is outside the range {range}",
+        secret.value()
+    );
+    let mut attempts = 0;
+
+    loop {
+        writeln!(
+            output,
+            "Please input your guess ({} to {}).",
+            range.min(),
+            range.max()
+        )?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output, "No more input, the secret was {}.", secret.value())?;
+            return Ok(Outcome::Quit { attempts });
+        }
+
+        let guess = match range.parse_guess(&line) {
+            Ok(guess) => guess,
+            Err(ParseGuessError::Parse(_)) => {
+                writeln!(output, "Parse error: '{}' is not a number.", line.trim())?;
+                continue;
+            }
+            Err(ParseGuessError::Range(error)) => {
+                writeln!(output, "Range error: {error}")?;
+                continue;
+            }
+        };
+
+        attempts += 1;
+
+        match guess.value().cmp(&secret.value()) {
+            Ordering::Less => writeln!(output, "Too low!")?,
+            Ordering::Greater => writeln!(output, "Too high!")?,
+            Ordering::Equal => {
+                writeln!(output, "You win!")?;
+                return Ok(Outcome::Won { attempts });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn run(input: &str, range: GuessRange, secret: i32) -> (Outcome, String) {
+        let secret = range.try_guess(secret).unwrap();
+        let mut output = Vec::new();
+        let outcome = play(Cursor::new(input), &mut output, range, secret).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn wins_after_hints() {
+        let (outcome, output) = run("50\n80\n66\n", GuessRange::DEFAULT, 66);
+
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        assert!(output.contains("Too low!"));
+        assert!(output.contains("Too high!"));
+        assert!(output.ends_with("You win!\n"));
+    }
+
+    #[test]
+    fn reprompts_on_parse_and_range_errors() {
+        let (outcome, output) = run("abc\n1000\n0\n66\n", GuessRange::DEFAULT, 66);
+
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+        assert!(output.contains("Parse error: 'abc' is not a number."));
+        assert!(output.contains("Range error: Guess value must be between 1 and 100. Got : 1000"));
+        assert!(output.contains("Guess value must be between 1 and 100. Got : 0"));
+        assert_eq!(output.matches("Please input your guess").count(), 4);
+    }
+
+    #[test]
+    fn uses_the_given_range() {
+        let (outcome, output) = run("7\n3\n", GuessRange::new(1, 6), 3);
+
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+        assert!(output.contains("Please input your guess (1 to 6)."));
+        assert!(output.contains("Got : 7"));
+    }
+
+    #[test]
+    fn quits_when_input_runs_out() {
+        let (outcome, output) = run("10\n", GuessRange::DEFAULT, 66);
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 1 });
+        assert!(output.contains("the secret was 66"));
+    }
+
+    #[test]
+    #[should_panic(expected = "the secret 100 is outside the range 1..=10")]
+    fn secret_must_be_in_the_range() {
+        let _ = play(
+            Cursor::new(""),
+            Vec::new(),
+            GuessRange::new(1, 10),
+            Guess::new(100),
+        );
+    }
+}
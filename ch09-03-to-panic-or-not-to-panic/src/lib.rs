@@ -1 +1,2 @@
 pub mod guessing_game;
+pub mod interactive;
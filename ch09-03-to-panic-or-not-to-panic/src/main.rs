@@ -1,6 +1,13 @@
-use std::net::IpAddr;
+use std::{
+    io,
+    net::IpAddr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use ch09_03_to_panic_or_not_to_panic::guessing_game::Guess;
+use ch09_03_to_panic_or_not_to_panic::{
+    guessing_game::{Guess, GuessRange},
+    interactive::{self, Outcome},
+};
 
 fn main() {
     // So how do you decide when to use `panic!` and when to return `Result`?
@@ -77,6 +84,7 @@ fn main() {
     let home: IpAddr = "127.0.0.1"
         .parse()
         .expect("Hardcoded IP address should be valid");
+    println!("Home address: {home}");
 
     //
     //
@@ -107,14 +115,39 @@ fn main() {
     // let guess: Guess = Guess::new(1000); // panics as it validates the value should be between 1 to 100
 
     let guess: Guess = Guess::new(12);
+    println!("Validated guess: {}", guess.value());
 
-    let secret_number = 66; // hardcoded here just for demonstrating example
-    if guess.value() > secret_number {
-        println!("Too high!");
-    } else if guess.value() < secret_number {
-        println!("Too low!");
-    } else {
-        println!("You win!");
+    // Guess::try_new returns a Result instead of panicking, so invalid input can be handled.
+    match Guess::try_new(1000) {
+        Ok(guess) => println!("Valid guess: {}", guess.value()),
+        Err(error) => println!("{error}"),
+    }
+
+    // Guesses for other ranges are built through a GuessRange,
+    // and parsing text goes straight to a validated Guess.
+    let dice = GuessRange::new(1, 6);
+    match dice.parse_guess("7") {
+        Ok(guess) => println!("Rolled {}", guess.value()),
+        Err(error) => println!("{error}"),
+    }
+
+    // The interactive game uses Guess for every line the player types, so bad input is
+    // reported and asked for again instead of panicking. This crate has no rand dependency,
+    // so the clock's nanoseconds stand in for a random secret.
+    let range = GuessRange::DEFAULT;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let secret = range
+        .try_guess(range.min() + (nanos % (range.max() - range.min() + 1) as u32) as i32)
+        .expect("secret is computed inside the range");
+
+    let stdin = io::stdin();
+    match interactive::play(stdin.lock(), io::stdout(), range, secret) {
+        Ok(Outcome::Won { attempts }) => println!("Found it in {attempts} attempts."),
+        Ok(Outcome::Quit { .. }) => {}
+        Err(error) => println!("Game stopped: {error}"),
     }
 
     // Rust’s error-handling features help you write more robust code.